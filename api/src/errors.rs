@@ -0,0 +1,84 @@
+use axum::Json;
+use axum::response::{IntoResponse, Response};
+use http::StatusCode;
+use http::header::{CACHE_CONTROL, PRAGMA, WWW_AUTHENTICATE};
+use serde::Serialize;
+
+#[derive(Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OAuthErrorKind {
+    AccessDenied,
+    AuthorizationPending,
+    ExpiredToken,
+    InvalidClient,
+    InvalidClientMetadata,
+    InvalidGrant,
+    InvalidRedirectUri,
+    InvalidRequest,
+    InvalidScope,
+    ServerError,
+    SlowDown,
+    UnauthorizedClient,
+    UnsupportedGrantType,
+    UnsupportedResponseType,
+}
+
+impl OAuthErrorKind {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::InvalidClient => StatusCode::UNAUTHORIZED,
+            Self::ServerError => StatusCode::INTERNAL_SERVER_ERROR,
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct OAuthError {
+    error: OAuthErrorKind,
+    error_description: &'static str,
+}
+
+impl OAuthError {
+    pub fn new(error: OAuthErrorKind, error_description: &'static str) -> Self {
+        Self {
+            error,
+            error_description,
+        }
+    }
+}
+
+impl IntoResponse for OAuthError {
+    fn into_response(self) -> Response {
+        let mut response = (
+            self.error.status_code(),
+            [(CACHE_CONTROL, "no-store"), (PRAGMA, "no-cache")],
+            Json(self),
+        )
+            .into_response();
+
+        if response.status() == StatusCode::UNAUTHORIZED {
+            response
+                .headers_mut()
+                .insert(WWW_AUTHENTICATE, "Basic realm=\"oauth\"".parse().unwrap());
+        }
+
+        response
+    }
+}
+
+pub trait OrOAuthError<T> {
+    fn or_oauth_error(self, error: OAuthErrorKind, error_description: &'static str) -> Result<T, OAuthError>;
+}
+
+impl<T> OrOAuthError<T> for Option<T> {
+    fn or_oauth_error(self, error: OAuthErrorKind, error_description: &'static str) -> Result<T, OAuthError> {
+        self.ok_or_else(|| OAuthError::new(error, error_description))
+    }
+}
+
+impl<T, E> OrOAuthError<T> for Result<T, E> {
+    fn or_oauth_error(self, error: OAuthErrorKind, error_description: &'static str) -> Result<T, OAuthError> {
+        self.map_err(|_| OAuthError::new(error, error_description))
+    }
+}
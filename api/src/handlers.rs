@@ -1,12 +1,14 @@
 use axum::body::Body;
+use axum::extract::rejection::{FormRejection, JsonRejection};
 use axum::extract::{Path, Query};
-use axum::response::{IntoResponse, Result};
+use axum::response::{ErrorResponse, IntoResponse, Result};
 use axum::{Form, Json};
 use axum_extra::TypedHeader;
 use axum_extra::headers::Authorization;
-use chrono::{DateTime, Utc};
+use axum_extra::headers::authorization::Basic;
+use chrono::{DateTime, NaiveDate, Utc};
 use http::StatusCode;
-use http::header::{CONTENT_DISPOSITION, CONTENT_LENGTH, CONTENT_TYPE};
+use http::header::{CACHE_CONTROL, CONTENT_DISPOSITION, CONTENT_LENGTH, CONTENT_TYPE, PRAGMA, WWW_AUTHENTICATE};
 use serde::Serialize;
 use url::Url;
 use uuid::Uuid;
@@ -14,16 +16,92 @@ use uuid::Uuid;
 use toolbox::axum::{AuthorizationBearer, OrHttpError};
 use toolbox::constants::{RESPONSE_ERROR_BAD_REQUEST, RESPONSE_ERROR_UNAUTHORIZED};
 
-use identity_core::models::User;
+use identity_core::config::{API_CONFIG, APP_CONFIG, JWT_CONFIG};
+use identity_core::enums::{Permission, Scope};
+use identity_core::models::{Application, ApplicationToken, User};
+use identity_core::params::{ApplicationParams, is_valid_redirect_url};
 use identity_core::{Info, commands};
 
-use crate::params::{AvatarImageParams, RevokeParams, TokenGrantType, TokenParams};
+use crate::errors::{OAuthError, OAuthErrorKind, OrOAuthError};
+use crate::params::{
+    ClientMetadataParams, DeviceAuthorizationParams, ImageParams, IntrospectParams, ParParams, RevokeParams,
+    TokenGrantType, TokenParams, TokenTypeHint,
+};
 
 #[derive(Serialize)]
 pub struct UserJson {
     id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    email: Option<String>,
+    #[serde(flatten)]
+    profile: Option<UserProfileJson>,
+}
+
+impl UserJson {
+    fn new(user: User<'_>, scopes: &[String]) -> Self {
+        let has_scope = |scope: Scope| scopes.iter().any(|value| value == scope.as_str());
+
+        Self {
+            id: user.id,
+            email: has_scope(Scope::Email).then(|| user.email.to_string()),
+            profile: has_scope(Scope::Profile).then(|| UserProfileJson {
+                username: user.username.to_string(),
+                display_name: user.display_name.to_string(),
+                initials: user.initials(),
+                language_code: user.language_code.to_string(),
+                country_code: user.country_code.to_string(),
+                avatar_image_url: user.avatar_image_url(),
+                created_at: user.created_at,
+                updated_at: user.updated_at,
+            }),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct UserInfoJson {
+    sub: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    email_verified: Option<bool>,
+    #[serde(flatten)]
+    profile: Option<UserInfoProfileJson>,
+}
+
+impl UserInfoJson {
+    fn new(user: User<'_>, scopes: &[String]) -> Self {
+        let has_scope = |scope: Scope| scopes.iter().any(|value| value == scope.as_str());
+
+        Self {
+            sub: user.id,
+            email: has_scope(Scope::Email).then(|| user.email.to_string()),
+            email_verified: has_scope(Scope::Email).then(|| user.email_is_confirmed()),
+            profile: has_scope(Scope::Profile).then(|| UserInfoProfileJson {
+                preferred_username: user.username.to_string(),
+                name: user.full_name.to_string(),
+                given_name: user.full_name.split_whitespace().next().unwrap_or_default().to_owned(),
+                locale: user.language_code.to_string(),
+                picture: user.avatar_image_url(),
+                birthdate: user.birthdate,
+            }),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct UserInfoProfileJson {
+    preferred_username: String,
+    name: String,
+    given_name: String,
+    locale: String,
+    picture: Url,
+    birthdate: NaiveDate,
+}
+
+#[derive(Serialize)]
+pub struct UserProfileJson {
     username: String,
-    email: String,
     display_name: String,
     initials: String,
     language_code: String,
@@ -33,39 +111,257 @@ pub struct UserJson {
     updated_at: Option<DateTime<Utc>>,
 }
 
-impl From<User<'_>> for UserJson {
-    fn from(user: User<'_>) -> Self {
-        Self {
-            id: user.id,
-            username: user.username.to_string(),
-            email: user.email.to_string(),
-            display_name: user.display_name.to_string(),
-            initials: user.initials(),
-            language_code: user.language_code.to_string(),
-            country_code: user.country_code.to_string(),
-            avatar_image_url: user.avatar_image_url(),
-            created_at: user.created_at,
-            updated_at: user.updated_at,
+async fn authenticate_client(
+    basic: Option<TypedHeader<Authorization<Basic>>>,
+    client_id: Option<Uuid>,
+    client_secret: Option<String>,
+) -> Result<Application<'static>, OAuthError> {
+    let (client_id, client_secret) = match basic {
+        Some(TypedHeader(Authorization(basic))) => {
+            let basic_client_id = basic
+                .username()
+                .parse::<Uuid>()
+                .or_oauth_error(OAuthErrorKind::InvalidClient, "Client authentication failed")?;
+
+            if client_id.is_some_and(|client_id| client_id != basic_client_id) {
+                return Err(OAuthError::new(
+                    OAuthErrorKind::InvalidRequest,
+                    "client_id does not match the client credentials",
+                ));
+            }
+
+            (basic_client_id, Some(basic.password().to_owned()))
         }
+        None => (
+            client_id.or_oauth_error(OAuthErrorKind::InvalidRequest, "client_id is required")?,
+            client_secret,
+        ),
+    };
+
+    let application = commands::get_application_by_id(client_id)
+        .await
+        .or_oauth_error(OAuthErrorKind::InvalidClient, "Client authentication failed")?;
+
+    match client_secret {
+        Some(client_secret) if application.verify_secret(&client_secret) => Ok(application),
+        None if !application.is_confidential() => Ok(application),
+        _ => Err(OAuthError::new(
+            OAuthErrorKind::InvalidClient,
+            "Client authentication failed",
+        )),
     }
 }
 
-async fn require_token(bearer: Option<AuthorizationBearer>) -> Result<impl IntoResponse> {
+fn application_params_from_metadata(
+    metadata: ClientMetadataParams,
+    application: Option<&Application<'_>>,
+) -> Result<ApplicationParams, OAuthError> {
+    if metadata.redirect_uris.is_empty()
+        || metadata
+            .redirect_uris
+            .iter()
+            .any(|redirect_uri| !is_valid_redirect_url(redirect_uri))
+    {
+        return Err(OAuthError::new(
+            OAuthErrorKind::InvalidRedirectUri,
+            "redirect_uris must contain https or loopback http URLs",
+        ));
+    }
+
+    if metadata
+        .post_logout_redirect_uris
+        .iter()
+        .any(|redirect_uri| !is_valid_redirect_url(redirect_uri))
+    {
+        return Err(OAuthError::new(
+            OAuthErrorKind::InvalidRedirectUri,
+            "post_logout_redirect_uris must contain https or loopback http URLs",
+        ));
+    }
+
+    let confidential = match metadata.token_endpoint_auth_method.as_deref() {
+        None | Some("client_secret_basic") | Some("client_secret_post") => true,
+        Some("none") => false,
+        _ => {
+            return Err(OAuthError::new(
+                OAuthErrorKind::InvalidClientMetadata,
+                "token_endpoint_auth_method is not supported",
+            ));
+        }
+    };
+
+    Ok(ApplicationParams {
+        name: metadata
+            .client_name
+            .or_oauth_error(OAuthErrorKind::InvalidClientMetadata, "client_name is required")?,
+        redirect_urls: metadata.redirect_uris,
+        trusted: application.is_some_and(|application| application.is_trusted()),
+        allowed_scopes: metadata
+            .scope
+            .map(|scope| scope.split_whitespace().map(|value| value.to_owned()).collect())
+            .unwrap_or_else(Scope::all_strings),
+        confidential,
+        jwt_access_tokens: application.is_some_and(|application| application.uses_jwt_access_tokens()),
+        par_required: application.is_some_and(|application| application.requires_par()),
+        backchannel_logout_url: metadata.backchannel_logout_uri,
+        post_logout_redirect_urls: metadata.post_logout_redirect_uris,
+        description: application.and_then(|application| application.description.clone()),
+        homepage_url: metadata.client_uri,
+        privacy_policy_url: metadata.policy_uri,
+        terms_url: metadata.tos_uri,
+        logo: None,
+        owner_id: application.and_then(|application| application.owner_id),
+        access_token_ttl_secs: application.and_then(|application| application.access_token_ttl_secs),
+        refresh_token_ttl_secs: application.and_then(|application| application.refresh_token_ttl_secs),
+        refresh_token_idle_ttl_secs: application.and_then(|application| application.refresh_token_idle_ttl_secs),
+    })
+}
+
+fn bearer_error(status_code: StatusCode, error: &str) -> ErrorResponse {
+    (
+        status_code,
+        [(WWW_AUTHENTICATE, format!("Bearer error=\"{error}\""))],
+        Json(serde_json::json!({ "error": error })),
+    )
+        .into()
+}
+
+fn client_information_json(application: &Application<'_>) -> serde_json::Value {
+    serde_json::json!({
+        "client_id": application.id,
+        "client_id_issued_at": application.created_at.timestamp(),
+        "client_name": application.name,
+        "redirect_uris": application.redirect_urls,
+        "scope": application.allowed_scopes.join(" "),
+        "token_endpoint_auth_method": if application.is_confidential() {
+            "client_secret_basic"
+        } else {
+            "none"
+        },
+        "grant_types": ["authorization_code", "refresh_token"],
+        "response_types": ["code"],
+        "backchannel_logout_uri": application.backchannel_logout_url,
+        "backchannel_logout_session_required": true,
+        "post_logout_redirect_uris": application.post_logout_redirect_urls,
+        "client_uri": application.homepage_url,
+        "policy_uri": application.privacy_policy_url,
+        "tos_uri": application.terms_url,
+        "logo_uri": application.has_logo().then(|| application.logo_image_url()),
+        "registration_client_uri": API_CONFIG.endpoint_url(&format!("oauth/register/{}", application.id)),
+    })
+}
+
+fn no_store<T: IntoResponse>(response: T) -> impl IntoResponse {
+    ([(CACHE_CONTROL, "no-store"), (PRAGMA, "no-cache")], response)
+}
+
+async fn require_application_token(bearer: Option<AuthorizationBearer>) -> Result<ApplicationToken<'static>> {
     let Some(TypedHeader(Authorization(bearer))) = bearer else {
         return Err(RESPONSE_ERROR_UNAUTHORIZED.clone().into());
     };
 
-    let code = bearer.token().to_owned();
-
-    commands::get_user_by_access_token_code(code.clone())
+    commands::get_application_token_by_code(bearer.token())
         .await
-        .map(|_| ())
-        .or(commands::get_application_token_by_code(code).await.map(|_| ()))
         .or_unauthorized()
 }
 
+async fn require_registration_access_token(
+    bearer: Option<AuthorizationBearer>,
+    client_id: Uuid,
+) -> Result<(ApplicationToken<'static>, Application<'static>)> {
+    let application_token = require_application_token(bearer).await?;
+
+    if application_token.application_id != client_id || !application_token.has_permission(Permission::ClientsManage) {
+        return Err(bearer_error(StatusCode::UNAUTHORIZED, "invalid_token"));
+    }
+
+    let application = commands::get_application_by_id(client_id).await.or_unauthorized()?;
+
+    Ok((application_token, application))
+}
+
+async fn require_token(
+    bearer: Option<AuthorizationBearer>,
+    permission: Option<Permission>,
+) -> Result<(Vec<String>, Option<Uuid>)> {
+    let Some(TypedHeader(Authorization(bearer))) = bearer else {
+        return Err(RESPONSE_ERROR_UNAUTHORIZED.clone().into());
+    };
+
+    let code = bearer.token();
+
+    if let Ok(access_token) = commands::get_access_token_by_code(code).await
+        && access_token.user().await.is_ok()
+    {
+        if permission.is_some_and(|permission| !permission.scope().is_some_and(|scope| access_token.has_scope(scope))) {
+            return Err(bearer_error(StatusCode::FORBIDDEN, "insufficient_scope"));
+        }
+
+        return Ok((access_token.scopes, Some(access_token.user_id)));
+    }
+
+    let application_token = commands::get_application_token_by_code(code).await.or_unauthorized()?;
+
+    if permission.is_some_and(|permission| !application_token.has_permission(permission)) {
+        return Err(bearer_error(StatusCode::FORBIDDEN, "insufficient_scope"));
+    }
+
+    let mut scopes = application_token.scopes;
+
+    if !application_token.has_permission(Permission::UsersReadEmail) {
+        scopes.retain(|scope| scope != Scope::Email.as_str());
+    }
+
+    Ok((scopes, None))
+}
+
+pub async fn delete_oauth_register_client(
+    bearer: Option<AuthorizationBearer>,
+    Path(client_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let (registration_access_token, application) = require_registration_access_token(bearer, client_id).await?;
+
+    commands::revoke_application_token(&registration_access_token)
+        .await
+        .or_internal_server_error()?;
+    commands::delete_application(application)
+        .await
+        .or_internal_server_error()?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn get_application_logo_image(
+    Path(id): Path<Uuid>,
+    Query(params): Query<ImageParams>,
+) -> Result<impl IntoResponse> {
+    let size = params.size.unwrap_or(128);
+
+    if size > 512 {
+        return Err(RESPONSE_ERROR_BAD_REQUEST.clone().into());
+    }
+
+    let application = commands::get_application_by_id(id).await.or_not_found()?;
+
+    let logo_image = application.logo_image(size).or_internal_server_error()?;
+
+    let content_length = logo_image.len();
+    let body = Body::from(logo_image);
+
+    let headers = [
+        (CONTENT_TYPE, "image/png".to_owned()),
+        (CONTENT_LENGTH, content_length.to_string()),
+        (
+            CONTENT_DISPOSITION,
+            format!("inline; filename=\"{}_{}x{}.png\"", id, size, size),
+        ),
+    ];
+
+    Ok((headers, body))
+}
+
 pub async fn get_authorized(authorization: Option<AuthorizationBearer>) -> Result<impl IntoResponse> {
-    require_token(authorization)
+    require_token(authorization, None)
         .await
         .map(|_| (StatusCode::OK, "\"Authorized\""))
 }
@@ -75,33 +371,123 @@ pub async fn get_current_user(authorization: Option<AuthorizationBearer>) -> Res
         return Err(RESPONSE_ERROR_UNAUTHORIZED.clone().into());
     };
 
-    let code = bearer.token().to_owned();
-
-    let user = commands::get_user_by_access_token_code(code).await.or_unauthorized()?;
+    let access_token = commands::get_access_token_by_code(bearer.token())
+        .await
+        .or_unauthorized()?;
+    let user = access_token.user().await.or_unauthorized()?;
 
-    Ok(Json(UserJson::from(user)))
+    Ok(Json(UserJson::new(user, &access_token.scopes)))
 }
 
 pub async fn get_index() -> impl IntoResponse {
     Json(Info::default())
 }
 
+pub async fn get_jwks() -> impl IntoResponse {
+    Json(commands::jwk_set())
+}
+
+pub async fn get_oauth_register_client(
+    bearer: Option<AuthorizationBearer>,
+    Path(client_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let (_, application) = require_registration_access_token(bearer, client_id).await?;
+
+    Ok(no_store(Json(client_information_json(&application))))
+}
+
+pub async fn get_oauth_userinfo(bearer: Option<AuthorizationBearer>) -> Result<impl IntoResponse> {
+    let Some(TypedHeader(Authorization(bearer))) = bearer else {
+        return Err((StatusCode::UNAUTHORIZED, [(WWW_AUTHENTICATE, "Bearer".to_owned())]).into());
+    };
+
+    let Ok(access_token) = commands::get_access_token_by_code(bearer.token()).await else {
+        return Err(bearer_error(StatusCode::UNAUTHORIZED, "invalid_token"));
+    };
+
+    let Ok(user) = access_token.user().await else {
+        return Err(bearer_error(StatusCode::UNAUTHORIZED, "invalid_token"));
+    };
+
+    if !access_token.has_scope(Scope::Openid) {
+        return Err(bearer_error(StatusCode::FORBIDDEN, "insufficient_scope"));
+    }
+
+    Ok(no_store(Json(UserInfoJson::new(user, &access_token.scopes))))
+}
+
+pub async fn get_openid_configuration() -> impl IntoResponse {
+    Json(serde_json::json!({
+        "issuer": API_CONFIG.issuer(),
+        "authorization_endpoint": APP_CONFIG.page_url("oauth/authorize"),
+        "token_endpoint": API_CONFIG.endpoint_url("oauth/token"),
+        "revocation_endpoint": API_CONFIG.endpoint_url("oauth/revoke"),
+        "introspection_endpoint": API_CONFIG.endpoint_url("oauth/introspect"),
+        "userinfo_endpoint": API_CONFIG.endpoint_url("oauth/userinfo"),
+        "device_authorization_endpoint": API_CONFIG.endpoint_url("oauth/device_authorization"),
+        "pushed_authorization_request_endpoint": API_CONFIG.endpoint_url("oauth/par"),
+        "registration_endpoint": API_CONFIG.endpoint_url("oauth/register"),
+        "end_session_endpoint": APP_CONFIG.page_url("oauth/logout"),
+        "require_pushed_authorization_requests": false,
+        "jwks_uri": API_CONFIG.endpoint_url(".well-known/jwks.json"),
+        "response_types_supported": ["code"],
+        "grant_types_supported": [
+            "authorization_code",
+            "client_credentials",
+            "refresh_token",
+            "urn:ietf:params:oauth:grant-type:device_code",
+        ],
+        "code_challenge_methods_supported": ["S256"],
+        "authorization_response_iss_parameter_supported": true,
+        "backchannel_logout_supported": true,
+        "backchannel_logout_session_supported": true,
+        "token_endpoint_auth_methods_supported": ["client_secret_basic", "client_secret_post", "none"],
+        "revocation_endpoint_auth_methods_supported": ["client_secret_basic", "client_secret_post", "none"],
+        "introspection_endpoint_auth_methods_supported": ["bearer"],
+        "subject_types_supported": ["public"],
+        "id_token_signing_alg_values_supported": [JWT_CONFIG.algorithm()],
+        "scopes_supported": Scope::all_strings(),
+        "claims_supported": [
+            "iss",
+            "sub",
+            "aud",
+            "exp",
+            "iat",
+            "auth_time",
+            "nonce",
+            "sid",
+            "preferred_username",
+            "name",
+            "given_name",
+            "email",
+            "email_verified",
+            "locale",
+            "picture",
+            "birthdate",
+        ],
+    }))
+}
+
 pub async fn get_user(
     bearer: Option<AuthorizationBearer>,
     Path(username_or_id): Path<String>,
 ) -> Result<impl IntoResponse> {
-    require_token(bearer).await?;
+    let (scopes, user_id) = require_token(bearer, Some(Permission::UsersRead)).await?;
 
     let user = commands::get_user_by_username_or_id(&username_or_id)
         .await
         .or_not_found()?;
 
-    Ok(Json(UserJson::from(user)))
+    if user_id.is_some_and(|user_id| user_id != user.id) {
+        return Err(bearer_error(StatusCode::FORBIDDEN, "insufficient_scope"));
+    }
+
+    Ok(Json(UserJson::new(user, &scopes)))
 }
 
 pub async fn get_user_avatar_image(
     Path(username_or_id): Path<String>,
-    Query(params): Query<AvatarImageParams>,
+    Query(params): Query<ImageParams>,
 ) -> Result<impl IntoResponse> {
     let size = params.size.unwrap_or(128);
 
@@ -130,70 +516,495 @@ pub async fn get_user_avatar_image(
     Ok((headers, body))
 }
 
-pub async fn post_oauth_revoke(Form(params): Form<RevokeParams>) -> Result<impl IntoResponse> {
-    let access_token = commands::get_access_token_by_code(params.token)
+pub async fn post_oauth_device_authorization(
+    basic: Option<TypedHeader<Authorization<Basic>>>,
+    form: Result<Form<DeviceAuthorizationParams>, FormRejection>,
+) -> Result<impl IntoResponse, OAuthError> {
+    let Form(params) = form.or_oauth_error(OAuthErrorKind::InvalidRequest, "Request body is invalid")?;
+
+    let client = authenticate_client(basic, params.client_id, params.client_secret).await?;
+
+    let scopes = client
+        .granted_scopes(params.scope.as_deref())
+        .or_oauth_error(OAuthErrorKind::InvalidScope, "Requested scope is invalid")?;
+
+    let device_authorization = commands::insert_device_authorization(&client, &scopes)
         .await
-        .or_bad_request()?;
+        .or_oauth_error(OAuthErrorKind::ServerError, "Could not create device authorization")?;
 
-    if params.client_id != access_token.application_id {
-        return Err(RESPONSE_ERROR_BAD_REQUEST.clone().into());
+    let verification_uri = APP_CONFIG.page_url("device");
+    let mut verification_uri_complete = verification_uri.clone();
+
+    verification_uri_complete.set_query(Some(&format!("user_code={}", device_authorization.user_code)));
+
+    Ok(Json(serde_json::json!({
+        "device_code": device_authorization.device_code,
+        "user_code": device_authorization.user_code,
+        "verification_uri": verification_uri,
+        "verification_uri_complete": verification_uri_complete,
+        "expires_in": device_authorization.expires_in().num_seconds(),
+        "interval": device_authorization.interval().as_secs(),
+    })))
+}
+
+pub async fn post_oauth_introspect(
+    bearer: Option<AuthorizationBearer>,
+    Form(params): Form<IntrospectParams>,
+) -> Result<impl IntoResponse> {
+    let application_token = require_application_token(bearer).await?;
+
+    if !application_token.has_permission(Permission::TokensIntrospect) {
+        return Err(bearer_error(StatusCode::FORBIDDEN, "insufficient_scope"));
+    }
+
+    if let Ok(access_token) = commands::get_access_token_by_code(&params.token).await
+        && access_token.user().await.is_ok()
+    {
+        return Ok(Json(serde_json::json!({
+            "active": true,
+            "sub": access_token.user_id,
+            "client_id": access_token.application_id,
+            "exp": access_token.code_expires_at.timestamp(),
+            "iat": access_token.created_at.timestamp(),
+            "scope": access_token.scopes.join(" "),
+            "token_type": "Bearer",
+        })));
+    }
+
+    if let Ok(application_token) = commands::get_application_token_by_code(&params.token).await {
+        return Ok(Json(serde_json::json!({
+            "active": true,
+            "sub": application_token.application_id,
+            "client_id": application_token.application_id,
+            "exp": application_token.expires_at.timestamp(),
+            "iat": application_token.created_at.timestamp(),
+            "scope": application_token.scopes.join(" "),
+            "token_type": "Bearer",
+        })));
     }
 
-    let _ = commands::revoke_access_token(&access_token).await;
+    Ok(Json(serde_json::json!({ "active": false })))
+}
+
+pub async fn post_oauth_par(
+    basic: Option<TypedHeader<Authorization<Basic>>>,
+    form: Result<Form<ParParams>, FormRejection>,
+) -> Result<impl IntoResponse, OAuthError> {
+    let Form(params) = form.or_oauth_error(OAuthErrorKind::InvalidRequest, "Request body is invalid")?;
+
+    let client = authenticate_client(basic, params.client_id, params.client_secret).await?;
+
+    if params.response_type.as_deref() != Some("code") {
+        return Err(OAuthError::new(
+            OAuthErrorKind::UnsupportedResponseType,
+            "response_type must be code",
+        ));
+    }
+
+    let redirect_uri = params
+        .redirect_uri
+        .filter(|redirect_uri| client.has_redirect_url(redirect_uri))
+        .or_oauth_error(OAuthErrorKind::InvalidRequest, "redirect_uri is not registered")?;
+
+    let code_challenge = params
+        .code_challenge
+        .filter(|_| params.code_challenge_method.as_deref() == Some("S256"))
+        .or_oauth_error(
+            OAuthErrorKind::InvalidRequest,
+            "code_challenge with S256 method is required",
+        )?;
+
+    if client.granted_scopes(params.scope.as_deref()).is_none() {
+        return Err(OAuthError::new(
+            OAuthErrorKind::InvalidScope,
+            "Requested scope is invalid",
+        ));
+    }
+
+    let pushed_authorization_request = commands::insert_pushed_authorization_request(
+        &client,
+        &redirect_uri,
+        &code_challenge,
+        params.scope.as_deref(),
+        params.nonce.as_deref(),
+        params.state.as_deref(),
+        params.prompt.as_deref(),
+        params.max_age,
+        params.login_hint.as_deref(),
+    )
+    .await
+    .or_oauth_error(
+        OAuthErrorKind::ServerError,
+        "Could not create pushed authorization request",
+    )?;
+
+    Ok((
+        StatusCode::CREATED,
+        no_store(Json(serde_json::json!({
+            "request_uri": pushed_authorization_request.request_uri,
+            "expires_in": pushed_authorization_request.expires_in().num_seconds(),
+        }))),
+    ))
+}
+
+pub async fn post_oauth_register(
+    bearer: Option<AuthorizationBearer>,
+    json: Result<Json<ClientMetadataParams>, JsonRejection>,
+) -> Result<impl IntoResponse> {
+    let initial_access_token = require_application_token(bearer).await?;
+
+    if !initial_access_token.has_permission(Permission::ClientsRegister) {
+        return Err(bearer_error(StatusCode::FORBIDDEN, "insufficient_scope"));
+    }
+
+    let Json(metadata) = json.or_oauth_error(OAuthErrorKind::InvalidClientMetadata, "Request body is invalid")?;
+
+    let application = commands::insert_application(application_params_from_metadata(metadata, None)?)
+        .await
+        .or_oauth_error(OAuthErrorKind::InvalidClientMetadata, "Client metadata is invalid")?;
+    let registration_access_token = commands::insert_registration_access_token(&application)
+        .await
+        .or_oauth_error(
+            OAuthErrorKind::ServerError,
+            "Could not create registration access token",
+        )?;
+
+    let mut response = client_information_json(&application);
+
+    response["registration_access_token"] = registration_access_token.code.to_string().into();
+
+    if application.is_confidential() {
+        let client_secret = commands::reset_application_secret(&application)
+            .await
+            .or_oauth_error(OAuthErrorKind::ServerError, "Could not create client secret")?;
+
+        response["client_secret"] = client_secret.into();
+        response["client_secret_expires_at"] = 0.into();
+    }
+
+    Ok((StatusCode::CREATED, no_store(Json(response))))
+}
+
+pub async fn post_oauth_revoke(
+    basic: Option<TypedHeader<Authorization<Basic>>>,
+    form: Result<Form<RevokeParams>, FormRejection>,
+) -> Result<impl IntoResponse, OAuthError> {
+    let Form(params) = form.or_oauth_error(OAuthErrorKind::InvalidRequest, "Request body is invalid")?;
+
+    let client = authenticate_client(basic, params.client_id, params.client_secret).await?;
+
+    let access_token = match params.token_type_hint {
+        Some(TokenTypeHint::RefreshToken) => match commands::get_access_token_by_refresh_code(&params.token).await {
+            Ok(access_token) => Ok(access_token),
+            Err(_) => commands::get_access_token_by_code(&params.token).await,
+        },
+        _ => match commands::get_access_token_by_code(&params.token).await {
+            Ok(access_token) => Ok(access_token),
+            Err(_) => commands::get_access_token_by_refresh_code(&params.token).await,
+        },
+    };
+
+    let Ok(access_token) = access_token else {
+        return Ok(Json(serde_json::json!({})));
+    };
+
+    if client.id != access_token.application_id {
+        return Err(OAuthError::new(
+            OAuthErrorKind::InvalidRequest,
+            "Token was not issued to this client",
+        ));
+    }
+
+    commands::revoke_access_token(&access_token)
+        .await
+        .or_oauth_error(OAuthErrorKind::ServerError, "Could not revoke token")?;
 
     Ok(Json(serde_json::json!({})))
 }
 
-pub async fn post_oauth_token(Form(params): Form<TokenParams>) -> Result<impl IntoResponse> {
-    let (application, authorization, session) = match params.grant_type {
+pub async fn post_oauth_token(
+    basic: Option<TypedHeader<Authorization<Basic>>>,
+    form: Result<Form<TokenParams>, FormRejection>,
+) -> Result<impl IntoResponse, OAuthError> {
+    let Form(params) = form.or_oauth_error(OAuthErrorKind::InvalidRequest, "Request body is invalid")?;
+
+    let client = authenticate_client(basic, params.client_id, params.client_secret).await?;
+
+    let (application, authorization, session, refreshed_access_token) = match params.grant_type {
         TokenGrantType::AuthorizationCode => {
-            let authorization_code = params.code.or_bad_request()?;
+            let authorization_code = params
+                .code
+                .or_oauth_error(OAuthErrorKind::InvalidRequest, "code is required")?;
 
-            let authorization = commands::get_authorization_by_code(authorization_code)
+            let authorization = commands::get_authorization_by_code(&authorization_code)
                 .await
-                .or_bad_request()?;
+                .or_oauth_error(
+                    OAuthErrorKind::InvalidGrant,
+                    "Authorization code is invalid or has expired",
+                )?;
+
+            let code_verifier = params
+                .code_verifier
+                .or_oauth_error(OAuthErrorKind::InvalidRequest, "code_verifier is required")?;
+
+            if client.id != authorization.application_id
+                || params
+                    .redirect_uri
+                    .as_ref()
+                    .is_none_or(|redirect_uri| !client.has_redirect_url(redirect_uri))
+                || params.redirect_uri != authorization.redirect_url()
+            {
+                return Err(OAuthError::new(
+                    OAuthErrorKind::InvalidGrant,
+                    "Authorization code was not issued to this client or redirect_uri",
+                ));
+            }
 
-            let code_verifier = params.code_verifier.or_bad_request()?;
+            if !authorization.verify_code_challenge(&code_verifier) {
+                return Err(OAuthError::new(
+                    OAuthErrorKind::InvalidGrant,
+                    "code_verifier does not match the code challenge",
+                ));
+            }
 
-            if params.client_id != authorization.application_id
-                || params.redirect_uri != Some(authorization.redirect_url())
-                || !authorization.verify_code_challenge(&code_verifier)
+            if !commands::redeem_authorization_code(&authorization)
+                .await
+                .or_oauth_error(OAuthErrorKind::ServerError, "Could not redeem authorization code")?
             {
-                return Err(RESPONSE_ERROR_BAD_REQUEST.clone().into());
+                let _ = commands::revoke_replayed_authorization_code(&authorization).await;
+
+                return Err(OAuthError::new(
+                    OAuthErrorKind::InvalidGrant,
+                    "Authorization code has already been used",
+                ));
             }
 
-            let application = authorization.application().await.or_internal_server_error()?;
-            let session = authorization.session().await.or_bad_request()?;
+            let application = authorization
+                .application()
+                .await
+                .or_oauth_error(OAuthErrorKind::ServerError, "Could not get application")?;
+            let session = authorization
+                .session()
+                .await
+                .or_oauth_error(OAuthErrorKind::InvalidGrant, "Session has expired")?;
 
-            (application, authorization, session)
+            (application, authorization, session, None)
         }
-        TokenGrantType::RefreshToken => {
-            let refresh_code = params.refresh_token.or_bad_request()?;
+        TokenGrantType::ClientCredentials => {
+            if !client.is_confidential() {
+                return Err(OAuthError::new(
+                    OAuthErrorKind::UnauthorizedClient,
+                    "Client is not allowed to use this grant type",
+                ));
+            }
+
+            let scopes = client
+                .granted_scopes(params.scope.as_deref())
+                .or_oauth_error(OAuthErrorKind::InvalidScope, "Requested scope is invalid")?
+                .into_iter()
+                .filter(|scope| scope != Scope::Openid.as_str() && scope != Scope::OfflineAccess.as_str())
+                .collect::<Vec<_>>();
 
-            let current_access_token = commands::get_access_token_by_refresh_code(refresh_code)
+            let application_token = commands::insert_client_credentials_token(&client, &scopes)
                 .await
-                .or_bad_request()?;
+                .or_oauth_error(OAuthErrorKind::ServerError, "Could not create access token")?;
+
+            return Ok(no_store(Json(serde_json::json!({
+                "access_token": application_token.code,
+                "token_type": "Bearer",
+                "expires_in": application_token.expires_in().num_seconds(),
+                "scope": application_token.scopes.join(" "),
+            }))));
+        }
+        TokenGrantType::DeviceCode => {
+            let device_code = params
+                .device_code
+                .or_oauth_error(OAuthErrorKind::InvalidRequest, "device_code is required")?;
+
+            let device_authorization = commands::get_device_authorization_by_device_code(&device_code)
+                .await
+                .or_oauth_error(OAuthErrorKind::InvalidGrant, "Device code is invalid")?;
+
+            if device_authorization.is_expired() {
+                return Err(OAuthError::new(OAuthErrorKind::ExpiredToken, "Device code has expired"));
+            }
+
+            if client.id != device_authorization.application_id {
+                return Err(OAuthError::new(
+                    OAuthErrorKind::InvalidGrant,
+                    "Device code was not issued to this client",
+                ));
+            }
+
+            if device_authorization.is_denied() {
+                return Err(OAuthError::new(
+                    OAuthErrorKind::AccessDenied,
+                    "User denied the authorization request",
+                ));
+            }
+
+            let Some(authorization_id) = device_authorization.authorization_id else {
+                let is_polled_too_soon = device_authorization.is_polled_too_soon();
+
+                commands::update_device_authorization_polled_at(&device_authorization, is_polled_too_soon)
+                    .await
+                    .or_oauth_error(OAuthErrorKind::ServerError, "Could not update device authorization")?;
+
+                return Err(if is_polled_too_soon {
+                    OAuthError::new(OAuthErrorKind::SlowDown, "Polling too frequently")
+                } else {
+                    OAuthError::new(
+                        OAuthErrorKind::AuthorizationPending,
+                        "User has not yet completed the authorization request",
+                    )
+                });
+            };
+
+            commands::delete_device_authorization(&device_authorization)
+                .await
+                .or_oauth_error(OAuthErrorKind::InvalidGrant, "Device code has already been used")?;
+
+            let authorization = commands::get_authorization_by_id(authorization_id)
+                .await
+                .or_oauth_error(OAuthErrorKind::InvalidGrant, "Authorization has been revoked")?;
+            let session = authorization
+                .session()
+                .await
+                .or_oauth_error(OAuthErrorKind::InvalidGrant, "Session has expired")?;
+
+            (client, authorization, session, None)
+        }
+        TokenGrantType::RefreshToken => {
+            let refresh_code = params
+                .refresh_token
+                .or_oauth_error(OAuthErrorKind::InvalidRequest, "refresh_token is required")?;
+
+            let Ok(current_access_token) = commands::get_access_token_by_refresh_code(&refresh_code).await else {
+                if let Ok(refreshed_access_token) =
+                    commands::get_refreshed_access_token_by_refresh_code(&refresh_code).await
+                {
+                    let _ = commands::revoke_reused_access_token(&refreshed_access_token).await;
+                }
+
+                return Err(OAuthError::new(
+                    OAuthErrorKind::InvalidGrant,
+                    "Refresh token is invalid or has expired",
+                ));
+            };
+
+            if client.id != current_access_token.application_id {
+                return Err(OAuthError::new(
+                    OAuthErrorKind::InvalidGrant,
+                    "Refresh token was not issued to this client",
+                ));
+            }
 
-            if params.client_id != current_access_token.application_id {
-                return Err(RESPONSE_ERROR_BAD_REQUEST.clone().into());
+            if !current_access_token.has_scope(Scope::OfflineAccess) {
+                return Err(OAuthError::new(
+                    OAuthErrorKind::InvalidGrant,
+                    "Refresh token was not granted the offline_access scope",
+                ));
             }
 
-            let application = current_access_token.application().await.or_internal_server_error()?;
-            let authorization = current_access_token.authorization().await.or_bad_request()?;
-            let session = current_access_token.session().await.or_bad_request()?;
+            let application = current_access_token
+                .application()
+                .await
+                .or_oauth_error(OAuthErrorKind::ServerError, "Could not get application")?;
+            let authorization = current_access_token
+                .authorization()
+                .await
+                .or_oauth_error(OAuthErrorKind::InvalidGrant, "Authorization has been revoked")?;
+            let session = current_access_token
+                .session()
+                .await
+                .or_oauth_error(OAuthErrorKind::InvalidGrant, "Session has expired")?;
+
+            if !commands::refresh_access_token(&current_access_token)
+                .await
+                .or_oauth_error(OAuthErrorKind::ServerError, "Could not refresh access token")?
+            {
+                let _ = commands::revoke_reused_access_token(&current_access_token).await;
+
+                return Err(OAuthError::new(
+                    OAuthErrorKind::InvalidGrant,
+                    "Refresh token has already been used",
+                ));
+            }
 
-            (application, authorization, session)
+            (application, authorization, session, Some(current_access_token))
+        }
+        TokenGrantType::Unsupported => {
+            return Err(OAuthError::new(
+                OAuthErrorKind::UnsupportedGrantType,
+                "grant_type is not supported",
+            ));
         }
     };
 
-    let access_token = commands::insert_access_token(&application, &authorization, &session)
-        .await
-        .or_bad_request()?;
+    let access_token =
+        commands::insert_access_token(&application, &authorization, &session, refreshed_access_token.as_ref())
+            .await
+            .or_oauth_error(OAuthErrorKind::ServerError, "Could not create access token")?;
 
-    Ok(Json(serde_json::json!({
+    let mut response = serde_json::json!({
         "access_token": access_token.code,
         "token_type": "Bearer",
         "expires_in": access_token.code_expires_in().num_seconds(),
-        "refresh_token": access_token.refresh_code,
-    })))
+        "scope": access_token.scopes.join(" "),
+    });
+
+    if access_token.has_scope(Scope::OfflineAccess) {
+        response["refresh_token"] = access_token.refresh_code.to_string().into();
+    }
+
+    if access_token.has_scope(Scope::Openid) {
+        let id_token = commands::encode_id_token(&authorization, &session)
+            .await
+            .or_oauth_error(OAuthErrorKind::ServerError, "Could not create ID token")?;
+
+        response["id_token"] = id_token.into();
+    }
+
+    Ok(no_store(Json(response)))
+}
+
+pub async fn put_oauth_register_client(
+    bearer: Option<AuthorizationBearer>,
+    Path(client_id): Path<Uuid>,
+    json: Result<Json<ClientMetadataParams>, JsonRejection>,
+) -> Result<impl IntoResponse> {
+    let (_, application) = require_registration_access_token(bearer, client_id).await?;
+
+    let Json(metadata) = json.or_oauth_error(OAuthErrorKind::InvalidClientMetadata, "Request body is invalid")?;
+
+    if metadata
+        .client_id
+        .is_some_and(|metadata_client_id| metadata_client_id != client_id)
+    {
+        return Err(OAuthError::new(
+            OAuthErrorKind::InvalidClientMetadata,
+            "client_id does not match the registration",
+        )
+        .into());
+    }
+
+    let application = commands::update_application(
+        &application,
+        application_params_from_metadata(metadata, Some(&application))?,
+    )
+    .await
+    .or_oauth_error(OAuthErrorKind::InvalidClientMetadata, "Client metadata is invalid")?;
+
+    let mut response = client_information_json(&application);
+
+    if application.is_confidential() && application.encrypted_secret.is_none() {
+        let client_secret = commands::reset_application_secret(&application)
+            .await
+            .or_oauth_error(OAuthErrorKind::ServerError, "Could not create client secret")?;
+
+        response["client_secret"] = client_secret.into();
+        response["client_secret_expires_at"] = 0.into();
+    }
+
+    Ok(no_store(Json(response)))
 }
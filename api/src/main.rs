@@ -13,10 +13,19 @@ use toolbox::tracing::start_tracing_subscriber;
 
 use identity_core::config::API_CONFIG;
 
+mod errors;
 mod handlers;
 mod params;
 
-use handlers::{get_current_user, get_index, get_user, get_user_avatar_image, post_oauth_revoke, post_oauth_token};
+#[cfg(test)]
+mod tests;
+
+use handlers::{
+    delete_oauth_register_client, get_application_logo_image, get_authorized, get_current_user, get_index, get_jwks,
+    get_oauth_register_client, get_oauth_userinfo, get_openid_configuration, get_user, get_user_avatar_image,
+    post_oauth_device_authorization, post_oauth_introspect, post_oauth_par, post_oauth_register, post_oauth_revoke,
+    post_oauth_token, put_oauth_register_client,
+};
 
 #[tokio::main]
 async fn main() {
@@ -24,15 +33,29 @@ async fn main() {
 
     let cors_layer = CorsLayer::new()
         .allow_origin(Any)
-        .allow_methods([Method::GET, Method::POST])
+        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
         .allow_headers(Any);
 
     let router = Router::new()
         .route("/", get(get_index))
-        .route("/authorized", get(handlers::get_authorized))
+        .route("/.well-known/jwks.json", get(get_jwks))
+        .route("/.well-known/openid-configuration", get(get_openid_configuration))
+        .route("/applications/{id}/logo-image", get(get_application_logo_image))
+        .route("/authorized", get(get_authorized))
         .route("/current-user", get(get_current_user))
+        .route("/oauth/device_authorization", post(post_oauth_device_authorization))
+        .route("/oauth/introspect", post(post_oauth_introspect))
+        .route("/oauth/par", post(post_oauth_par))
+        .route("/oauth/register", post(post_oauth_register))
+        .route(
+            "/oauth/register/{client_id}",
+            get(get_oauth_register_client)
+                .put(put_oauth_register_client)
+                .delete(delete_oauth_register_client),
+        )
         .route("/oauth/revoke", post(post_oauth_revoke))
         .route("/oauth/token", post(post_oauth_token))
+        .route("/oauth/userinfo", get(get_oauth_userinfo).post(get_oauth_userinfo))
         .route("/users/{username_or_id}", get(get_user))
         .route("/users/{username_or_id}/avatar-image", get(get_user_avatar_image))
         .layer(SentryHttpLayer::new().enable_transaction())
@@ -6,26 +6,89 @@ use uuid::Uuid;
 #[serde(rename_all = "snake_case")]
 pub enum TokenGrantType {
     AuthorizationCode,
+    ClientCredentials,
+    #[serde(rename = "urn:ietf:params:oauth:grant-type:device_code")]
+    DeviceCode,
     RefreshToken,
+    #[serde(other)]
+    Unsupported,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenTypeHint {
+    AccessToken,
+    RefreshToken,
+    #[serde(other)]
+    Unsupported,
+}
+
+#[derive(Deserialize)]
+pub struct ClientMetadataParams {
+    pub client_id: Option<Uuid>,
+    pub client_name: Option<String>,
+    #[serde(default)]
+    pub redirect_uris: Vec<String>,
+    pub scope: Option<String>,
+    pub token_endpoint_auth_method: Option<String>,
+    pub backchannel_logout_uri: Option<String>,
+    #[serde(default)]
+    pub post_logout_redirect_uris: Vec<String>,
+    pub client_uri: Option<String>,
+    pub policy_uri: Option<String>,
+    pub tos_uri: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct DeviceAuthorizationParams {
+    pub client_id: Option<Uuid>,
+    pub client_secret: Option<String>,
+    pub scope: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct ImageParams {
+    pub size: Option<u32>,
+}
+
+#[derive(Deserialize)]
+pub struct IntrospectParams {
+    pub token: String,
+}
+
+#[derive(Deserialize)]
+pub struct ParParams {
+    pub client_id: Option<Uuid>,
+    pub client_secret: Option<String>,
+    pub response_type: Option<String>,
+    pub redirect_uri: Option<Url>,
+    pub code_challenge: Option<String>,
+    pub code_challenge_method: Option<String>,
+    pub scope: Option<String>,
+    pub nonce: Option<String>,
+    pub state: Option<String>,
+    pub prompt: Option<String>,
+    pub max_age: Option<i32>,
+    pub login_hint: Option<String>,
 }
 
 #[derive(Deserialize)]
 pub struct RevokeParams {
-    pub client_id: Uuid,
+    pub client_id: Option<Uuid>,
+    pub client_secret: Option<String>,
     pub token: String,
+    pub token_type_hint: Option<TokenTypeHint>,
 }
 
 #[derive(Deserialize)]
 pub struct TokenParams {
     pub grant_type: TokenGrantType,
-    pub client_id: Uuid,
+    pub client_id: Option<Uuid>,
+    pub client_secret: Option<String>,
     pub code: Option<String>,
     pub redirect_uri: Option<Url>,
     pub refresh_token: Option<String>,
     pub code_verifier: Option<String>,
-}
-
-#[derive(Deserialize)]
-pub struct AvatarImageParams {
-    pub size: Option<u32>,
+    pub scope: Option<String>,
+    pub device_code: Option<String>,
 }
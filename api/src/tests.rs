@@ -0,0 +1,365 @@
+use std::net::{IpAddr, Ipv4Addr};
+
+use axum::Form;
+use axum::response::IntoResponse;
+use chrono::NaiveDate;
+use http::StatusCode;
+use uuid::Uuid;
+
+use identity_core::commands;
+use identity_core::enums::Scope;
+use identity_core::models::{Application, Session};
+use identity_core::params::{ApplicationParams, UserParams};
+
+use crate::handlers::post_oauth_token;
+use crate::params::{TokenGrantType, TokenParams};
+
+const REDIRECT_URL: &str = "https://example.com/callback";
+// Example from RFC 7636, Appendix B.
+const CODE_CHALLENGE: &str = "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM";
+const CODE_VERIFIER: &str = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+
+async fn insert_application(confidential: bool) -> Application<'static> {
+    commands::insert_application(ApplicationParams {
+        name: "Test application".to_owned(),
+        redirect_urls: vec![REDIRECT_URL.to_owned()],
+        trusted: false,
+        allowed_scopes: Scope::all_strings(),
+        confidential,
+        jwt_access_tokens: false,
+        par_required: false,
+        backchannel_logout_url: None,
+        post_logout_redirect_urls: Vec::new(),
+        description: None,
+        homepage_url: None,
+        privacy_policy_url: None,
+        terms_url: None,
+        logo: None,
+        owner_id: None,
+        access_token_ttl_secs: None,
+        refresh_token_ttl_secs: None,
+        refresh_token_idle_ttl_secs: None,
+    })
+    .await
+    .expect("Could not insert application")
+}
+
+async fn insert_authorization_code(application: &Application<'_>, session: &Session, scopes: &[&str]) -> String {
+    let scopes = scopes.iter().map(|scope| scope.to_string()).collect::<Vec<_>>();
+    let authorization = commands::insert_or_refresh_authorization(
+        application,
+        session,
+        &REDIRECT_URL.parse().unwrap(),
+        CODE_CHALLENGE,
+        &scopes,
+        None,
+        None,
+    )
+    .await
+    .expect("Could not insert authorization");
+
+    authorization.code.to_string()
+}
+
+async fn insert_session() -> Session {
+    let username = format!("t{}", &Uuid::new_v4().simple().to_string()[..12]);
+    let user = commands::insert_user(UserParams {
+        username: username.clone(),
+        email: format!("{username}@example.com"),
+        password: "password".to_owned(),
+        full_name: "Test User".to_owned(),
+        birthdate: NaiveDate::from_ymd_opt(2000, 1, 1),
+        country_code: "US".to_owned(),
+    })
+    .await
+    .expect("Could not insert user");
+
+    commands::insert_session(&user, IpAddr::V4(Ipv4Addr::LOCALHOST))
+        .await
+        .expect("Could not insert session")
+}
+
+async fn post_token(params: TokenParams) -> (StatusCode, serde_json::Value) {
+    let response = post_oauth_token(None, Ok(Form(params))).await.into_response();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Could not read response body");
+
+    (
+        status,
+        serde_json::from_slice(&body).expect("Response body is not JSON"),
+    )
+}
+
+fn token_params(grant_type: TokenGrantType, client_id: Uuid) -> TokenParams {
+    TokenParams {
+        grant_type,
+        client_id: Some(client_id),
+        client_secret: None,
+        code: None,
+        redirect_uri: None,
+        refresh_token: None,
+        code_verifier: None,
+        scope: None,
+        device_code: None,
+    }
+}
+
+fn authorization_code_params(client_id: Uuid, code: &str, code_verifier: &str) -> TokenParams {
+    TokenParams {
+        code: Some(code.to_owned()),
+        redirect_uri: Some(REDIRECT_URL.parse().unwrap()),
+        code_verifier: Some(code_verifier.to_owned()),
+        ..token_params(TokenGrantType::AuthorizationCode, client_id)
+    }
+}
+
+fn refresh_token_params(client_id: Uuid, refresh_token: &serde_json::Value) -> TokenParams {
+    TokenParams {
+        refresh_token: refresh_token.as_str().map(|value| value.to_owned()),
+        ..token_params(TokenGrantType::RefreshToken, client_id)
+    }
+}
+
+fn device_code_params(client_id: Uuid, device_code: &str) -> TokenParams {
+    TokenParams {
+        device_code: Some(device_code.to_owned()),
+        ..token_params(TokenGrantType::DeviceCode, client_id)
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "requires PostgreSQL and Redis"]
+async fn authorization_code_grant_issues_tokens_for_the_granted_scopes() {
+    let application = insert_application(false).await;
+    let session = insert_session().await;
+    let code = insert_authorization_code(&application, &session, &["profile", "offline_access"]).await;
+
+    let (status, body) = post_token(authorization_code_params(application.id, &code, CODE_VERIFIER)).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["access_token"].is_string());
+    assert!(body["refresh_token"].is_string());
+    assert!(body.get("id_token").is_none());
+    assert_eq!(body["token_type"], "Bearer");
+    assert_eq!(body["scope"], "profile offline_access");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "requires PostgreSQL and Redis"]
+async fn authorization_code_grant_omits_refresh_token_without_offline_access() {
+    let application = insert_application(false).await;
+    let session = insert_session().await;
+    let code = insert_authorization_code(&application, &session, &["profile"]).await;
+
+    let (status, body) = post_token(authorization_code_params(application.id, &code, CODE_VERIFIER)).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["access_token"].is_string());
+    assert!(body.get("refresh_token").is_none());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "requires PostgreSQL and Redis"]
+async fn authorization_code_grant_rejects_an_invalid_code_verifier() {
+    let application = insert_application(false).await;
+    let session = insert_session().await;
+    let code = insert_authorization_code(&application, &session, &["profile"]).await;
+
+    let (status, body) = post_token(authorization_code_params(application.id, &code, "invalid")).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "invalid_grant");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "requires PostgreSQL and Redis"]
+async fn authorization_code_grant_rejects_another_client() {
+    let application = insert_application(false).await;
+    let other_application = insert_application(false).await;
+    let session = insert_session().await;
+    let code = insert_authorization_code(&application, &session, &["profile"]).await;
+
+    let (status, body) = post_token(authorization_code_params(other_application.id, &code, CODE_VERIFIER)).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "invalid_grant");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "requires PostgreSQL and Redis"]
+async fn authorization_code_grant_revokes_issued_tokens_on_replay() {
+    let application = insert_application(false).await;
+    let session = insert_session().await;
+    let code = insert_authorization_code(&application, &session, &["profile", "offline_access"]).await;
+
+    let (status, body) = post_token(authorization_code_params(application.id, &code, CODE_VERIFIER)).await;
+
+    assert_eq!(status, StatusCode::OK);
+
+    let access_token = body["access_token"].as_str().unwrap().to_owned();
+
+    assert!(commands::get_access_token_by_code(&access_token).await.is_ok());
+
+    let (status, body) = post_token(authorization_code_params(application.id, &code, CODE_VERIFIER)).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "invalid_grant");
+    assert!(commands::get_access_token_by_code(&access_token).await.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "requires PostgreSQL and Redis"]
+async fn authorization_code_replay_keeps_tokens_from_other_codes() {
+    let application = insert_application(false).await;
+    let session = insert_session().await;
+    let first_code = insert_authorization_code(&application, &session, &["profile"]).await;
+
+    let (_, body) = post_token(authorization_code_params(application.id, &first_code, CODE_VERIFIER)).await;
+    let first_access_token = body["access_token"].as_str().unwrap().to_owned();
+
+    let second_code = insert_authorization_code(&application, &session, &["profile"]).await;
+
+    let (_, body) = post_token(authorization_code_params(application.id, &second_code, CODE_VERIFIER)).await;
+    let second_access_token = body["access_token"].as_str().unwrap().to_owned();
+
+    let (status, _) = post_token(authorization_code_params(application.id, &second_code, CODE_VERIFIER)).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(commands::get_access_token_by_code(&first_access_token).await.is_ok());
+    assert!(commands::get_access_token_by_code(&second_access_token).await.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "requires PostgreSQL and Redis"]
+async fn refresh_token_grant_rotates_and_revokes_the_family_on_reuse() {
+    let application = insert_application(false).await;
+    let session = insert_session().await;
+    let code = insert_authorization_code(&application, &session, &["profile", "offline_access"]).await;
+
+    let (_, body) = post_token(authorization_code_params(application.id, &code, CODE_VERIFIER)).await;
+    let first_refresh_token = body["refresh_token"].clone();
+
+    let (status, body) = post_token(refresh_token_params(application.id, &first_refresh_token)).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_ne!(body["refresh_token"], first_refresh_token);
+
+    let second_refresh_token = body["refresh_token"].clone();
+
+    let (status, body) = post_token(refresh_token_params(application.id, &first_refresh_token)).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "invalid_grant");
+
+    let (status, body) = post_token(refresh_token_params(application.id, &second_refresh_token)).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "invalid_grant");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "requires PostgreSQL and Redis"]
+async fn client_credentials_grant_requires_a_confidential_client() {
+    let application = insert_application(false).await;
+
+    let (status, body) = post_token(token_params(TokenGrantType::ClientCredentials, application.id)).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "unauthorized_client");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "requires PostgreSQL and Redis"]
+async fn client_credentials_grant_issues_an_application_token() {
+    let application = insert_application(true).await;
+    let client_secret = commands::reset_application_secret(&application)
+        .await
+        .expect("Could not reset application secret");
+
+    let (status, body) = post_token(TokenParams {
+        client_secret: Some(client_secret),
+        scope: Some("profile openid".to_owned()),
+        ..token_params(TokenGrantType::ClientCredentials, application.id)
+    })
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["access_token"].is_string());
+    assert!(body.get("refresh_token").is_none());
+    assert_eq!(body["scope"], "profile");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "requires PostgreSQL and Redis"]
+async fn client_credentials_grant_rejects_an_invalid_client_secret() {
+    let application = insert_application(true).await;
+
+    let (status, body) = post_token(TokenParams {
+        client_secret: Some("invalid".to_owned()),
+        ..token_params(TokenGrantType::ClientCredentials, application.id)
+    })
+    .await;
+
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body["error"], "invalid_client");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "requires PostgreSQL and Redis"]
+async fn device_code_grant_polls_until_approved() {
+    let application = insert_application(false).await;
+    let session = insert_session().await;
+    let device_authorization = commands::insert_device_authorization(&application, &["profile".to_owned()])
+        .await
+        .expect("Could not insert device authorization");
+    let device_code = device_authorization.device_code.to_string();
+
+    let (status, body) = post_token(device_code_params(application.id, &device_code)).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "authorization_pending");
+
+    let (status, body) = post_token(device_code_params(application.id, &device_code)).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "slow_down");
+
+    commands::approve_device_authorization(&device_authorization, &session)
+        .await
+        .expect("Could not approve device authorization");
+
+    let (status, body) = post_token(device_code_params(application.id, &device_code)).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["access_token"].is_string());
+    assert_eq!(body["scope"], "profile");
+
+    let (status, body) = post_token(device_code_params(application.id, &device_code)).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "invalid_grant");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "requires PostgreSQL and Redis"]
+async fn device_code_grant_rejects_an_unknown_device_code() {
+    let application = insert_application(false).await;
+
+    let (status, body) = post_token(device_code_params(application.id, "unknown")).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "invalid_grant");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "requires PostgreSQL and Redis"]
+async fn unsupported_grant_type_is_rejected() {
+    let application = insert_application(false).await;
+
+    let (status, body) = post_token(token_params(TokenGrantType::Unsupported, application.id)).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "unsupported_grant_type");
+}
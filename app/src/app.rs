@@ -87,9 +87,15 @@ pub fn App() -> impl IntoView {
                                 <Route path=StaticSegment("edit-profile") view=EditProfilePage />
                                 <Route path=StaticSegment("edit-email") view=EditEmailPage />
                                 <Route path=StaticSegment("change-password") view=ChangePasswordPage />
+                                <Route path=StaticSegment("connected-apps") view=ConnectedAppsPage />
+                                <Route path=path!("developer/applications") view=DeveloperApplicationsPage />
+                                <Route path=path!("developer/applications/:id") view=DeveloperApplicationPage />
                             </ParentRoute>
                             <Route path=path!("/oauth/authorize") view=AuthorizePage />
+                            <Route path=path!("/oauth/logout") view=EndSessionPage />
+                            <Route path=StaticSegment("device") view=DevicePage />
                             <Route path=StaticSegment("login") view=LoginPage />
+                            <Route path=StaticSegment("reauthenticate") view=ReauthenticatePage />
                             <Route path=StaticSegment("register") view=RegisterPage />
                             <Route path=StaticSegment("reset-password") view=ResetPasswordPage />
                         </Routes>
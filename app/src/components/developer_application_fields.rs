@@ -0,0 +1,75 @@
+use leptos::prelude::*;
+
+use crate::presenters::DeveloperApplicationPresenter;
+
+use super::{TextAreaField, TextField};
+
+#[component]
+pub fn DeveloperApplicationFields(
+    #[prop(optional)] application: Option<DeveloperApplicationPresenter>,
+    #[prop(into, optional)] disabled: Signal<bool>,
+    #[prop(into)] error: Callback<&'static str, Option<String>>,
+) -> impl IntoView {
+    let application = application.unwrap_or_default();
+
+    view! {
+        <TextField
+            disabled=disabled
+            label="Name"
+            name="name"
+            value=application.name
+            error=Signal::derive(move || error.run("name"))
+        />
+
+        <TextAreaField
+            disabled=disabled
+            label="Redirect URLs (one per line)"
+            name="redirect_urls"
+            value=application.redirect_urls.join("\n")
+            error=Signal::derive(move || error.run("redirect_urls"))
+        />
+
+        <TextAreaField
+            disabled=disabled
+            label="Post-logout redirect URLs (one per line)"
+            name="post_logout_redirect_urls"
+            value=application.post_logout_redirect_urls.join("\n")
+            error=Signal::derive(move || error.run("post_logout_redirect_urls"))
+        />
+
+        <TextAreaField
+            disabled=disabled
+            label="Description"
+            name="description"
+            value=application.description.unwrap_or_default()
+            error=Signal::derive(move || error.run("description"))
+        />
+
+        <TextField
+            disabled=disabled
+            label="Homepage URL"
+            input_type="url"
+            name="homepage_url"
+            value=application.homepage_url.unwrap_or_default()
+            error=Signal::derive(move || error.run("homepage_url"))
+        />
+
+        <TextField
+            disabled=disabled
+            label="Privacy policy URL"
+            input_type="url"
+            name="privacy_policy_url"
+            value=application.privacy_policy_url.unwrap_or_default()
+            error=Signal::derive(move || error.run("privacy_policy_url"))
+        />
+
+        <TextField
+            disabled=disabled
+            label="Terms of service URL"
+            input_type="url"
+            name="terms_url"
+            value=application.terms_url.unwrap_or_default()
+            error=Signal::derive(move || error.run("terms_url"))
+        />
+    }
+}
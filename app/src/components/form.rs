@@ -137,6 +137,35 @@ pub fn SubmitButton(
     }
 }
 
+#[component]
+pub fn TextAreaField(
+    #[prop(into, optional)] disabled: Signal<bool>,
+    #[prop(into, optional)] error: Signal<Option<String>>,
+    #[prop(optional)] id: String,
+    #[prop(into, optional)] label: String,
+    #[prop(default = 1000)] max_length: u16,
+    #[prop(into, optional)] name: String,
+    #[prop(default = 3)] rows: u8,
+    #[prop(into, optional)] value: Signal<String>,
+) -> impl IntoView {
+    view! {
+        <FormField disabled=disabled error=error label=label>
+            <textarea
+                class="textarea"
+                class:textarea-error=move || error.read().is_some()
+                disabled=disabled
+                id=id
+                maxlength=max_length
+                name=name
+                rows=rows
+                prop:value=value
+            >
+                {value.get_untracked()}
+            </textarea>
+        </FormField>
+    }
+}
+
 #[component]
 pub fn TextField(
     #[prop(into, optional)] disabled: Signal<bool>,
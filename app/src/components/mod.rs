@@ -4,16 +4,20 @@ use leptos::prelude::*;
 use crate::icons::{CheckCircleOutline, ExclamationOutline, InformationCircleOutline};
 
 mod current_user;
+mod developer_application_fields;
 mod form;
 mod logo;
 mod modal;
 mod navbar;
+mod scope_list;
 
 pub use current_user::CurrentUser;
+pub use developer_application_fields::DeveloperApplicationFields;
 pub use form::*;
 pub use logo::Mango3Logo;
 pub use modal::*;
 pub use navbar::Navbar;
+pub use scope_list::ScopeList;
 
 #[derive(Clone, Copy, Default)]
 pub enum AlertType {
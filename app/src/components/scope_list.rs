@@ -0,0 +1,20 @@
+use leptos::prelude::*;
+
+fn scope_description(scope: &str) -> &'static str {
+    match scope {
+        "email" => "View your email address",
+        "offline_access" => "Keep access to your account information while you are not using it",
+        "openid" => "Confirm your identity",
+        "profile" => "View your profile information (username, name, language, country and avatar)",
+        _ => "Unknown permission",
+    }
+}
+
+#[component]
+pub fn ScopeList(scopes: Vec<String>) -> impl IntoView {
+    view! {
+        <ul class="list-disc list-inside">
+            {scopes.iter().map(|scope| view! { <li>{scope_description(scope)}</li> }).collect::<Vec<_>>()}
+        </ul>
+    }
+}
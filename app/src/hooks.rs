@@ -9,6 +9,7 @@ use crate::server_fns::{self, ServerFnResult};
 #[derive(Clone, Default, Params, PartialEq)]
 pub struct LoginQuery {
     redirect_to: Option<String>,
+    login_hint: Option<String>,
 }
 
 #[derive(Clone, Copy, Default)]
@@ -56,6 +57,20 @@ pub fn use_current_user_resource() -> Resource<ServerFnResult<UserPresenter>> {
     expect_context()
 }
 
+pub fn use_login_hint() -> Memo<String> {
+    let query = use_query::<LoginQuery>();
+
+    Memo::new(move |_| {
+        query.with(|result| {
+            result
+                .as_ref()
+                .ok()
+                .and_then(|query| query.login_hint.clone())
+                .unwrap_or_default()
+        })
+    })
+}
+
 pub fn use_redirect_to() -> Memo<String> {
     let query = use_query::<LoginQuery>();
 
@@ -67,7 +82,7 @@ pub fn use_redirect_to() -> Memo<String> {
                 .and_then(|query| query.redirect_to.clone())
                 .unwrap_or("/".to_owned());
 
-            if value.starts_with("/login") || value.starts_with("/register") {
+            if value.starts_with("/login") || value.starts_with("/reauthenticate") || value.starts_with("/register") {
                 "/".to_owned()
             } else {
                 value
@@ -1,123 +1,496 @@
 use leptos::either::{Either, EitherOf5};
 use leptos::prelude::*;
-use leptos_router::hooks::use_query;
+use leptos_meta::Title;
+use leptos_router::hooks::{use_navigate, use_query, use_url};
+use leptos_router::location;
 use leptos_router::params::Params;
-use url::Url;
+use serde::{Deserialize, Serialize};
+use url::{Url, form_urlencoded};
 use uuid::Uuid;
 
-use crate::pages::AuthenticatedPage;
-use crate::presenters::ApplicationPresenter;
+use crate::components::ScopeList;
+use crate::hooks::use_current_user_resource;
+use crate::presenters::{ApplicationPresenter, PushedAuthorizationRequestPresenter};
 use crate::server_fns;
 
-#[derive(Clone, Default, Params, PartialEq)]
+#[derive(Clone, Default, Deserialize, Params, PartialEq, Serialize)]
 struct AuthorizeQuery {
     client_id: Option<Uuid>,
     redirect_uri: Option<Url>,
     response_type: Option<String>,
     code_challenge: Option<String>,
     code_challenge_method: Option<String>,
+    scope: Option<String>,
+    nonce: Option<String>,
+    state: Option<String>,
+    request_uri: Option<String>,
+    prompt: Option<String>,
+    max_age: Option<i64>,
+    login_hint: Option<String>,
+}
+
+impl AuthorizeQuery {
+    fn error(
+        &self,
+        application: &ApplicationPresenter,
+        login_required: bool,
+        scopes: Option<&[String]>,
+        consented: bool,
+    ) -> Option<&'static str> {
+        if self.has_prompt("none") {
+            if self.prompts().count() > 1 {
+                return Some("invalid_request");
+            }
+
+            if login_required {
+                return Some("login_required");
+            }
+
+            if !application.is_trusted && !consented {
+                return Some("consent_required");
+            }
+        }
+
+        if scopes.is_none() {
+            return Some("invalid_scope");
+        }
+
+        if self.request_uri.is_some() {
+            return None;
+        }
+
+        if self.response_type != Some("code".to_owned()) {
+            return Some("unsupported_response_type");
+        }
+
+        if application.requires_par
+            || self.code_challenge.is_none()
+            || self.code_challenge_method != Some("S256".to_owned())
+        {
+            return Some("invalid_request");
+        }
+
+        None
+    }
+
+    fn has_prompt(&self, value: &str) -> bool {
+        self.prompts().any(|prompt| prompt == value)
+    }
+
+    fn prompts(&self) -> impl Iterator<Item = &str> {
+        self.prompt.as_deref().unwrap_or_default().split_whitespace()
+    }
+
+    fn requires_login(&self, login_required: bool) -> bool {
+        // A pushed request keeps its prompt after login, so `login_required` already accounts for it.
+        login_required || (self.request_uri.is_none() && self.has_prompt("login"))
+    }
+
+    fn should_redirect(
+        &self,
+        application: &ApplicationPresenter,
+        login_required: bool,
+        scopes: Option<&[String]>,
+        consented: bool,
+    ) -> bool {
+        (application.is_trusted && !self.has_prompt("consent"))
+            || self.has_prompt("none")
+            || self.requires_login(login_required)
+            || self.error(application, login_required, scopes, consented).is_some()
+    }
+}
+
+impl From<PushedAuthorizationRequestPresenter> for AuthorizeQuery {
+    fn from(pushed_authorization_request: PushedAuthorizationRequestPresenter) -> Self {
+        AuthorizeQuery {
+            redirect_uri: Some(pushed_authorization_request.redirect_url),
+            scope: pushed_authorization_request.scope,
+            state: pushed_authorization_request.state,
+            request_uri: Some(pushed_authorization_request.request_uri),
+            prompt: pushed_authorization_request.prompt,
+            max_age: pushed_authorization_request.max_age,
+            login_hint: pushed_authorization_request.login_hint,
+            ..Default::default()
+        }
+    }
+}
+
+fn login_path(url: &location::Url, is_authenticated: bool, login_hint: Option<&str>) -> String {
+    let search = form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(
+            form_urlencoded::parse(url.search().as_bytes()).filter_map(|(key, value)| {
+                if key != "prompt" {
+                    return Some((key, value));
+                }
+
+                let value = value
+                    .split_whitespace()
+                    .filter(|prompt| *prompt != "login")
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                (!value.is_empty()).then(|| (key, value.into()))
+            }),
+        )
+        .finish();
+    let redirect_to = format!("{}?{}", url.path(), search);
+    let redirect_to = form_urlencoded::byte_serialize(redirect_to.as_bytes()).collect::<String>();
+
+    if is_authenticated {
+        return format!("/reauthenticate?redirect_to={}", redirect_to);
+    }
+
+    let mut login_path = format!("/login?redirect_to={}", redirect_to);
+
+    if let Some(login_hint) = login_hint {
+        login_path.push_str(&format!(
+            "&login_hint={}",
+            form_urlencoded::byte_serialize(login_hint.as_bytes()).collect::<String>()
+        ));
+    }
+
+    login_path
 }
 
 #[component]
 pub fn AuthorizePage() -> impl IntoView {
     let query = use_query::<AuthorizeQuery>();
-    let application_resource = Resource::new_blocking(
-        move || query.get().unwrap_or_default().client_id,
-        async move |id| {
-            let Some(id) = id else {
+    let url = use_url();
+    let navigate = use_navigate();
+    let current_user_resource = use_current_user_resource();
+    let authorize_resource = Resource::new_blocking(
+        move || query.get().unwrap_or_default(),
+        async move |query| {
+            let Some(client_id) = query.client_id else {
                 return Err(ServerFnError::Args("client_id is required".to_owned()));
             };
 
-            server_fns::application(id).await
+            let application = server_fns::application(client_id).await?;
+            let (query, authenticated_since) = match query.request_uri {
+                Some(request_uri) => {
+                    let pushed_authorization_request =
+                        server_fns::pushed_authorization_request(client_id, request_uri).await?;
+                    let created_at = pushed_authorization_request.created_at;
+                    let query = AuthorizeQuery {
+                        client_id: Some(client_id),
+                        ..AuthorizeQuery::from(pushed_authorization_request)
+                    };
+                    let authenticated_since = query.has_prompt("login").then_some(created_at);
+
+                    (query, authenticated_since)
+                }
+                None => (query, None),
+            };
+            let login_required = server_fns::login_required(query.max_age, authenticated_since).await?;
+            let scopes = server_fns::granted_scopes(client_id, query.scope.clone()).await?;
+            let consented = match scopes {
+                Some(ref scopes) => server_fns::has_consented(client_id, scopes.clone()).await?,
+                None => false,
+            };
+
+            Ok((application, query, login_required, scopes, consented))
         },
     );
-    let action = Action::new(move |query: &AuthorizeQuery| {
+    let action = Action::new(move |(query, error): &(AuthorizeQuery, Option<&'static str>)| {
         let query = query.to_owned();
+        let error = *error;
         async move {
-            if query.response_type != Some("code".to_owned()) || query.code_challenge_method != Some("S256".to_owned())
-            {
+            let (Some(application_id), Some(redirect_url)) = (query.client_id, query.redirect_uri) else {
                 return Err(ServerFnError::Args("Invalid arguments".to_owned()));
+            };
+
+            if let Some(error) = error {
+                return server_fns::authorization_error(application_id, redirect_url, error.to_owned(), query.state)
+                    .await;
+            }
+
+            if let Some(request_uri) = query.request_uri {
+                return server_fns::create_pushed_authorization(application_id, request_uri).await;
             }
 
-            let (Some(application_id), Some(redirect_url), Some(code_challenge)) =
-                (query.client_id, query.redirect_uri, query.code_challenge)
-            else {
+            let Some(code_challenge) = query.code_challenge else {
                 return Err(ServerFnError::Args("Invalid arguments".to_owned()));
             };
 
-            server_fns::create_authorization(application_id, redirect_url, code_challenge).await
+            server_fns::create_authorization(
+                application_id,
+                redirect_url,
+                code_challenge,
+                query.scope,
+                query.nonce,
+                query.state,
+            )
+            .await
         }
     });
     let action_value = action.value();
 
     Effect::watch(
-        move || application_resource.get(),
-        move |application, _, _| {
-            if let Some(Ok(ApplicationPresenter {
-                id: _,
-                name: _,
-                is_trusted: true,
-            })) = application
-            {
-                action.dispatch(query.get_untracked().unwrap_or_default());
+        move || authorize_resource.get(),
+        move |authorize, _, _| {
+            let Some(Ok((application, query, login_required, scopes, consented))) = authorize else {
+                return;
+            };
+
+            if let Some(error) = query.error(application, *login_required, scopes.as_deref(), *consented) {
+                action.dispatch((query.clone(), Some(error)));
+            } else if query.requires_login(*login_required) {
+                let is_authenticated = matches!(current_user_resource.get_untracked(), Some(Ok(_)));
+
+                navigate(
+                    &url.with_untracked(|url| login_path(url, is_authenticated, query.login_hint.as_deref())),
+                    Default::default(),
+                );
+            } else if query.should_redirect(application, *login_required, scopes.as_deref(), *consented) {
+                action.dispatch((query.clone(), None));
             }
         },
         false,
     );
 
     view! {
-        <AuthenticatedPage title="Authorize Application">
-            <Suspense>
-                {move || Suspend::new(async move {
-                    match (application_resource.get(), action_value.get()) {
-                        (Some(Ok(ApplicationPresenter { id: _, name: _, is_trusted: true })), None)
-                        | (Some(Ok(_)), Some(Ok(_))) => {
-                            EitherOf5::A(view! { <div class="text-center">"Redirecting..."</div> })
-                        }
-                        (Some(Ok(_)), Some(Err(_))) => {
-                            EitherOf5::B(view! { <div class="text-center">"Could not authorize application..."</div> })
-                        }
-                        (Some(Ok(application)), None) => {
-                            EitherOf5::C(
-                                view! {
-                                    <div class="card card-border bg-base-100 max-w-160 mx-auto my-4 w-full">
-                                        <div class="card-body">
-                                            <p class="text-xl">
-                                                "Authorize "<b>{application.name}</b>
-                                                " to use your user account information"
-                                            </p>
-
-                                            <div class="card-actions">
-                                                <button
-                                                    on:click=move |_| {
-                                                        action.dispatch(query.get_untracked().unwrap_or_default());
-                                                    }
-                                                    class="btn-submit"
-                                                    disabled=move || action.pending().get()
-                                                >
-                                                    {move || {
-                                                        if action.pending().get() {
-                                                            Either::Left(
-                                                                view! { <span class="loading loading-spinner" /> },
-                                                            )
-                                                        } else {
-                                                            Either::Right("Authorize")
-                                                        }
-                                                    }}
-                                                </button>
+        <Title text="Authorize Application" />
+
+        <h1 class="h1">"Authorize Application"</h1>
+
+        <Suspense>
+            {move || Suspend::new(async move {
+                match (authorize_resource.get(), action_value.get()) {
+                    (Some(Ok((application, query, login_required, scopes, consented))), None)
+                        if query.should_redirect(&application, login_required, scopes.as_deref(), consented) =>
+                    {
+                        EitherOf5::A(view! { <div class="text-center">"Redirecting..."</div> })
+                    }
+                    (Some(Ok(_)), Some(Ok(_))) => {
+                        EitherOf5::A(view! { <div class="text-center">"Redirecting..."</div> })
+                    }
+                    (Some(Ok(_)), Some(Err(_))) => {
+                        EitherOf5::B(view! { <div class="text-center">"Could not authorize application..."</div> })
+                    }
+                    (Some(Ok((application, query, _, scopes, _))), None) => {
+                        let scopes = scopes.unwrap_or_default();
+                        let deny_query = query.clone();
+
+                        EitherOf5::C(
+                            view! {
+                                <div class="card card-border bg-base-100 max-w-160 mx-auto my-4 w-full">
+                                    <div class="card-body">
+                                        <div class="flex items-center gap-3">
+                                            <div class="avatar">
+                                                <div class="rounded-box w-12">
+                                                    <img
+                                                        alt=application.name.clone()
+                                                        src=application.logo_image_url(96).to_string()
+                                                    />
+                                                </div>
                                             </div>
+
+                                            <p class="text-xl">"Authorize "<b>{application.name}</b>" to:"</p>
+                                        </div>
+
+                                        {application
+                                            .description
+                                            .map(|description| view! { <p class="opacity-75">{description}</p> })}
+
+                                        <ScopeList scopes=scopes />
+
+                                        <div class="flex flex-wrap gap-3 text-sm">
+                                            {application
+                                                .homepage_url
+                                                .map(|url| {
+                                                    view! {
+                                                        <a class="link" href=url.to_string() target="_blank">
+                                                            "Website"
+                                                        </a>
+                                                    }
+                                                })}
+                                            {application
+                                                .privacy_policy_url
+                                                .map(|url| {
+                                                    view! {
+                                                        <a class="link" href=url.to_string() target="_blank">
+                                                            "Privacy policy"
+                                                        </a>
+                                                    }
+                                                })}
+                                            {application
+                                                .terms_url
+                                                .map(|url| {
+                                                    view! {
+                                                        <a class="link" href=url.to_string() target="_blank">
+                                                            "Terms of service"
+                                                        </a>
+                                                    }
+                                                })}
+                                        </div>
+
+                                        <div class="card-actions">
+                                            <button
+                                                on:click=move |_| {
+                                                    action.dispatch((query.clone(), None));
+                                                }
+                                                class="btn-submit"
+                                                disabled=move || action.pending().get()
+                                            >
+                                                {move || {
+                                                    if action.pending().get() {
+                                                        Either::Left(
+                                                            view! { <span class="loading loading-spinner" /> },
+                                                        )
+                                                    } else {
+                                                        Either::Right("Authorize")
+                                                    }
+                                                }}
+                                            </button>
+
+                                            <button
+                                                on:click=move |_| {
+                                                    action.dispatch((deny_query.clone(), Some("access_denied")));
+                                                }
+                                                class="btn btn-outline"
+                                                disabled=move || action.pending().get()
+                                            >
+                                                "Deny"
+                                            </button>
                                         </div>
                                     </div>
-                                },
-                            )
-                        }
-                        (Some(Err(_)), _) => {
-                            EitherOf5::D(view! { <div class="text-center">"Application not found"</div> })
-                        }
-                        (_, _) => EitherOf5::E(()),
+                                </div>
+                            },
+                        )
+                    }
+                    (Some(Err(_)), _) => {
+                        EitherOf5::D(view! { <div class="text-center">"Application not found"</div> })
                     }
-                })}
-            </Suspense>
-        </AuthenticatedPage>
+                    (_, _) => EitherOf5::E(()),
+                }
+            })}
+        </Suspense>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn application(is_trusted: bool, requires_par: bool) -> ApplicationPresenter {
+        serde_json::from_value(serde_json::json!({
+            "id": Uuid::nil(),
+            "name": "Test",
+            "is_trusted": is_trusted,
+            "requires_par": requires_par,
+            "allowed_scopes": ["openid", "profile"],
+            "description": null,
+            "homepage_url": null,
+            "privacy_policy_url": null,
+            "terms_url": null,
+            "logo_image_url": "https://example.com/logo.png",
+        }))
+        .unwrap()
+    }
+
+    fn query(prompt: Option<&str>) -> AuthorizeQuery {
+        AuthorizeQuery {
+            client_id: Some(Uuid::nil()),
+            redirect_uri: Some("https://example.com/callback".parse().unwrap()),
+            response_type: Some("code".to_owned()),
+            code_challenge: Some("challenge".to_owned()),
+            code_challenge_method: Some("S256".to_owned()),
+            prompt: prompt.map(|prompt| prompt.to_owned()),
+            ..Default::default()
+        }
+    }
+
+    const SCOPES: &[String] = &[];
+
+    #[test]
+    fn error_accepts_a_valid_request() {
+        assert_eq!(
+            query(None).error(&application(false, false), false, Some(SCOPES), false),
+            None
+        );
+    }
+
+    #[test]
+    fn error_requires_code_response_type_and_pkce() {
+        let application = application(false, false);
+        let query_without_challenge = AuthorizeQuery {
+            code_challenge: None,
+            ..query(None)
+        };
+        let query_with_plain_challenge = AuthorizeQuery {
+            code_challenge_method: Some("plain".to_owned()),
+            ..query(None)
+        };
+        let query_with_token_response = AuthorizeQuery {
+            response_type: Some("token".to_owned()),
+            ..query(None)
+        };
+
+        assert_eq!(
+            query_without_challenge.error(&application, false, Some(SCOPES), false),
+            Some("invalid_request")
+        );
+        assert_eq!(
+            query_with_plain_challenge.error(&application, false, Some(SCOPES), false),
+            Some("invalid_request")
+        );
+        assert_eq!(
+            query_with_token_response.error(&application, false, Some(SCOPES), false),
+            Some("unsupported_response_type")
+        );
+    }
+
+    #[test]
+    fn error_requires_pushed_requests_when_the_application_requires_par() {
+        assert_eq!(
+            query(None).error(&application(false, true), false, Some(SCOPES), false),
+            Some("invalid_request")
+        );
+    }
+
+    #[test]
+    fn error_rejects_scopes_that_cannot_be_granted() {
+        assert_eq!(
+            query(None).error(&application(false, false), false, None, false),
+            Some("invalid_scope")
+        );
+    }
+
+    #[test]
+    fn error_with_prompt_none_requires_login_and_consent() {
+        let query = query(Some("none"));
+
+        assert_eq!(
+            query.error(&application(true, false), true, Some(SCOPES), false),
+            Some("login_required")
+        );
+        assert_eq!(
+            query.error(&application(false, false), false, Some(SCOPES), false),
+            Some("consent_required")
+        );
+        assert_eq!(query.error(&application(false, false), false, Some(SCOPES), true), None);
+        assert_eq!(query.error(&application(true, false), false, Some(SCOPES), false), None);
+    }
+
+    #[test]
+    fn error_rejects_prompt_none_combined_with_other_prompts() {
+        assert_eq!(
+            query(Some("none login")).error(&application(true, false), false, Some(SCOPES), true),
+            Some("invalid_request")
+        );
+    }
+
+    #[test]
+    fn requires_login_honors_prompt_login_and_max_age() {
+        assert!(query(Some("login")).requires_login(false));
+        assert!(query(None).requires_login(true));
+        assert!(!query(None).requires_login(false));
+        assert!(
+            !AuthorizeQuery {
+                request_uri: Some("urn:ietf:params:oauth:request_uri:test".to_owned()),
+                ..query(Some("login"))
+            }
+            .requires_login(false)
+        );
     }
 }
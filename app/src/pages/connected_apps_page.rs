@@ -0,0 +1,139 @@
+use leptos::either::EitherOf3;
+use leptos::prelude::*;
+use uuid::Uuid;
+
+use crate::components::{AlertType, ConfirmationModal, ScopeList};
+use crate::hooks::use_toast;
+use crate::presenters::ConnectedApplicationPresenter;
+use crate::server_fns;
+use crate::utils::format_datetime;
+
+use super::AuthenticatedPage;
+
+#[component]
+fn ConnectedApplicationCard(
+    connected_application: ConnectedApplicationPresenter,
+    #[prop(into)] disabled: Signal<bool>,
+    #[prop(into)] on_disconnect: Callback<Uuid>,
+) -> impl IntoView {
+    let authorization_id = connected_application.authorization_id;
+
+    view! {
+        <div class="card card-border bg-base-100 w-full">
+            <div class="card-body">
+                <h2 class="card-title">{connected_application.application.name}</h2>
+
+                <p>"First authorized: "{format_datetime(&connected_application.first_authorized_at)}</p>
+
+                <p>"Last authorized: "{format_datetime(&connected_application.last_authorized_at)}</p>
+
+                <ScopeList scopes=connected_application.scopes />
+
+                <p>{format!("Active tokens: {}", connected_application.access_tokens.len())}</p>
+
+                <ul class="list-disc list-inside">
+                    {connected_application
+                        .access_tokens
+                        .iter()
+                        .map(|access_token| {
+                            view! {
+                                <li>
+                                    {format!(
+                                        "Issued {}, expires {}",
+                                        format_datetime(&access_token.created_at),
+                                        format_datetime(&access_token.expires_at),
+                                    )}
+                                </li>
+                            }
+                        })
+                        .collect::<Vec<_>>()}
+                </ul>
+
+                <div class="card-actions">
+                    <button
+                        on:click=move |_| on_disconnect.run(authorization_id)
+                        class="btn btn-outline"
+                        disabled=disabled
+                    >
+                        "Disconnect"
+                    </button>
+                </div>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+pub fn ConnectedAppsPage() -> impl IntoView {
+    let mut toast = use_toast();
+    let connected_applications_resource = Resource::new_blocking(|| (), |_| server_fns::connected_applications());
+    let disconnect_authorization_id = RwSignal::new(None::<Uuid>);
+    let show_disconnect_confirmation = RwSignal::new(false);
+    let action = Action::new(move |authorization_id: &Uuid| {
+        let authorization_id = *authorization_id;
+        async move { server_fns::disconnect_application(authorization_id).await }
+    });
+    let action_value = action.value();
+
+    Effect::watch(
+        move || action_value.get(),
+        move |action_value, _, _| match action_value {
+            Some(Ok(_)) => {
+                connected_applications_resource.refetch();
+                toast.push_alert(AlertType::Success, "Application disconnected successfully");
+            }
+            Some(Err(_)) => toast.push_alert(AlertType::Error, "Failed to disconnect application"),
+            None => {}
+        },
+        false,
+    );
+
+    view! {
+        <AuthenticatedPage title="Connected Apps">
+            <Suspense>
+                {move || Suspend::new(async move {
+                    match connected_applications_resource.get() {
+                        Some(Ok(connected_applications)) if connected_applications.is_empty() => {
+                            EitherOf3::A(view! { <div>"You have not authorized any applications yet."</div> })
+                        }
+                        Some(Ok(connected_applications)) => {
+                            EitherOf3::B(
+                                view! {
+                                    <div class="flex flex-col gap-4">
+                                        {connected_applications
+                                            .into_iter()
+                                            .map(|connected_application| {
+                                                view! {
+                                                    <ConnectedApplicationCard
+                                                        connected_application=connected_application
+                                                        disabled=action.pending()
+                                                        on_disconnect=move |authorization_id| {
+                                                            disconnect_authorization_id.set(Some(authorization_id));
+                                                            show_disconnect_confirmation.set(true);
+                                                        }
+                                                    />
+                                                }
+                                            })
+                                            .collect::<Vec<_>>()}
+                                    </div>
+                                },
+                            )
+                        }
+                        _ => EitherOf3::C(()),
+                    }
+                })}
+            </Suspense>
+
+            <ConfirmationModal
+                is_open=show_disconnect_confirmation
+                on_accept=move |_| {
+                    if let Some(authorization_id) = disconnect_authorization_id.get_untracked() {
+                        action.dispatch(authorization_id);
+                    }
+                }
+            >
+                "Are you sure you want to disconnect this application? It will lose access to your account."
+            </ConfirmationModal>
+        </AuthenticatedPage>
+    }
+}
@@ -0,0 +1,237 @@
+use leptos::either::EitherOf3;
+use leptos::prelude::*;
+use leptos_router::hooks::use_params;
+use leptos_router::params::Params;
+use uuid::Uuid;
+
+use crate::components::{
+    Alert, AlertType, ConfirmationModal, DeveloperApplicationFields, ScopeList, SubmitButton, TextField,
+};
+use crate::hooks::use_toast;
+use crate::pages::AuthenticatedPage;
+use crate::presenters::ApplicationTokenPresenter;
+use crate::server_fns::{self, ActionResultExt, CreateDeveloperApplicationToken, UpdateDeveloperApplication};
+use crate::utils::format_datetime;
+
+#[derive(Clone, Default, Params, PartialEq)]
+struct DeveloperApplicationParams {
+    id: Option<Uuid>,
+}
+
+#[component]
+fn ApplicationTokenCard(
+    application_token: ApplicationTokenPresenter,
+    #[prop(into)] disabled: Signal<bool>,
+    #[prop(into)] on_revoke: Callback<Uuid>,
+) -> impl IntoView {
+    let application_token_id = application_token.id;
+
+    view! {
+        <div class="card card-border bg-base-100 w-full">
+            <div class="card-body">
+                <h3 class="card-title">{application_token.name}</h3>
+
+                <p>"Created: "{format_datetime(&application_token.created_at)}</p>
+
+                <p>"Expires: "{format_datetime(&application_token.expires_at)}</p>
+
+                <p>"Permissions: "{application_token.permissions.join(", ")}</p>
+
+                <ScopeList scopes=application_token.scopes />
+
+                <div class="card-actions">
+                    <button
+                        on:click=move |_| on_revoke.run(application_token_id)
+                        class="btn btn-outline"
+                        disabled=disabled
+                    >
+                        "Revoke"
+                    </button>
+                </div>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+pub fn DeveloperApplicationPage() -> impl IntoView {
+    let params = use_params::<DeveloperApplicationParams>();
+    let application_id = Memo::new(move |_| params.get().ok().and_then(|params| params.id).unwrap_or_default());
+    let mut toast = use_toast();
+    let developer_application_resource =
+        Resource::new_blocking(move || application_id.get(), server_fns::developer_application);
+    let application_tokens_resource =
+        Resource::new_blocking(move || application_id.get(), server_fns::developer_application_tokens);
+    let update_action = ServerAction::<UpdateDeveloperApplication>::new();
+    let update_action_value = update_action.value();
+    let create_token_action = ServerAction::<CreateDeveloperApplicationToken>::new();
+    let create_token_action_value = create_token_action.value();
+    let error_token_name = Memo::new(move |_| create_token_action_value.read().get_param_error("name"));
+    let error_token_expires_at = Memo::new(move |_| create_token_action_value.read().get_param_error("expires_at"));
+    let revoke_application_token_id = RwSignal::new(None::<Uuid>);
+    let show_revoke_confirmation = RwSignal::new(false);
+    let revoke_token_action = Action::new(move |application_token_id: &Uuid| {
+        let application_token_id = *application_token_id;
+        async move { server_fns::revoke_developer_application_token(application_token_id).await }
+    });
+    let revoke_token_action_value = revoke_token_action.value();
+
+    Effect::watch(
+        move || update_action_value.get(),
+        move |update_action_value, _, _| {
+            if update_action_value.is_success() {
+                developer_application_resource.refetch();
+                toast.push_alert(AlertType::Success, "Application updated successfully");
+            }
+        },
+        false,
+    );
+
+    Effect::watch(
+        move || create_token_action_value.get(),
+        move |create_token_action_value, _, _| {
+            if create_token_action_value.is_success() {
+                application_tokens_resource.refetch();
+            }
+        },
+        false,
+    );
+
+    Effect::watch(
+        move || revoke_token_action_value.get(),
+        move |revoke_token_action_value, _, _| match revoke_token_action_value {
+            Some(Ok(_)) => {
+                application_tokens_resource.refetch();
+                toast.push_alert(AlertType::Success, "Application token revoked successfully");
+            }
+            Some(Err(_)) => toast.push_alert(AlertType::Error, "Failed to revoke application token"),
+            None => {}
+        },
+        false,
+    );
+
+    view! {
+        <AuthenticatedPage title="Manage Application">
+            <Suspense>
+                {move || Suspend::new(async move {
+                    match developer_application_resource.get() {
+                        Some(Ok(application)) => {
+                            let id = application.id.to_string();
+
+                            EitherOf3::A(
+                                view! {
+                                    <p>"Client ID: "<code>{id.clone()}</code></p>
+
+                                    <ActionForm
+                                        action=update_action
+                                        attr:class="form"
+                                        attr:autocomplete="off"
+                                        attr:novalidate="true"
+                                    >
+                                        <Show when=move || update_action_value.read().has_errors()>
+                                            <Alert alert_type=AlertType::Error>"Failed to update application"</Alert>
+                                        </Show>
+
+                                        <input type="hidden" name="id" value=id />
+
+                                        <DeveloperApplicationFields
+                                            application=application
+                                            disabled=update_action.pending()
+                                            error=move |name| update_action_value.read().get_param_error(name)
+                                        />
+
+                                        <SubmitButton is_pending=update_action.pending() />
+                                    </ActionForm>
+                                },
+                            )
+                        }
+                        Some(Err(_)) => EitherOf3::B(view! { <div>"Application not found"</div> }),
+                        None => EitherOf3::C(()),
+                    }
+                })}
+            </Suspense>
+
+            <h2 class="h2 mt-6">"Application Tokens"</h2>
+
+            <Suspense>
+                {move || Suspend::new(async move {
+                    match application_tokens_resource.get() {
+                        Some(Ok(application_tokens)) if application_tokens.is_empty() => {
+                            EitherOf3::A(view! { <div>"This application has no active tokens."</div> })
+                        }
+                        Some(Ok(application_tokens)) => {
+                            EitherOf3::B(
+                                view! {
+                                    <div class="flex flex-col gap-4">
+                                        {application_tokens
+                                            .into_iter()
+                                            .map(|application_token| {
+                                                view! {
+                                                    <ApplicationTokenCard
+                                                        application_token=application_token
+                                                        disabled=revoke_token_action.pending()
+                                                        on_revoke=move |application_token_id| {
+                                                            revoke_application_token_id.set(Some(application_token_id));
+                                                            show_revoke_confirmation.set(true);
+                                                        }
+                                                    />
+                                                }
+                                            })
+                                            .collect::<Vec<_>>()}
+                                    </div>
+                                },
+                            )
+                        }
+                        _ => EitherOf3::C(()),
+                    }
+                })}
+            </Suspense>
+
+            <h3 class="h3 mt-6">"Create Application Token"</h3>
+
+            <ActionForm action=create_token_action attr:class="form" attr:autocomplete="off" attr:novalidate="true">
+                <Show when=move || create_token_action_value.read().has_errors()>
+                    <Alert alert_type=AlertType::Error>"Failed to create application token"</Alert>
+                </Show>
+
+                {move || {
+                    create_token_action_value
+                        .get()
+                        .and_then(|result| result.ok())
+                        .map(|code| {
+                            view! {
+                                <Alert alert_type=AlertType::Success>
+                                    "Token created (it will not be shown again): "<code>{code}</code>
+                                </Alert>
+                            }
+                        })
+                }}
+
+                <input type="hidden" name="application_id" value=move || application_id.get().to_string() />
+
+                <TextField disabled=create_token_action.pending() label="Name" name="name" error=error_token_name />
+
+                <TextField
+                    disabled=create_token_action.pending()
+                    label="Expires at"
+                    input_type="date"
+                    name="expires_at"
+                    error=error_token_expires_at
+                />
+
+                <SubmitButton is_pending=create_token_action.pending() />
+            </ActionForm>
+
+            <ConfirmationModal
+                is_open=show_revoke_confirmation
+                on_accept=move |_| {
+                    if let Some(application_token_id) = revoke_application_token_id.get_untracked() {
+                        revoke_token_action.dispatch(application_token_id);
+                    }
+                }
+            >
+                "Are you sure you want to revoke this token? Requests using it will stop working."
+            </ConfirmationModal>
+        </AuthenticatedPage>
+    }
+}
@@ -0,0 +1,96 @@
+use leptos::either::EitherOf4;
+use leptos::prelude::*;
+use leptos_router::components::A;
+use leptos_router::hooks::use_navigate;
+
+use crate::components::{Alert, AlertType, DeveloperApplicationFields, SubmitButton};
+use crate::hooks::use_toast;
+use crate::pages::AuthenticatedPage;
+use crate::presenters::DeveloperApplicationPresenter;
+use crate::server_fns::{self, ActionResultExt, CreateDeveloperApplication};
+
+#[component]
+fn DeveloperApplicationCard(application: DeveloperApplicationPresenter) -> impl IntoView {
+    view! {
+        <div class="card card-border bg-base-100 w-full">
+            <div class="card-body">
+                <h2 class="card-title">{application.name}</h2>
+
+                <p>"Client ID: "<code>{application.id.to_string()}</code></p>
+
+                <div class="card-actions">
+                    <A attr:class="btn btn-outline" href=format!("/developer/applications/{}", application.id)>
+                        "Manage"
+                    </A>
+                </div>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+pub fn DeveloperApplicationsPage() -> impl IntoView {
+    let navigate = use_navigate();
+    let mut toast = use_toast();
+    let developer_applications_resource = Resource::new_blocking(|| (), |_| server_fns::developer_applications());
+    let action = ServerAction::<CreateDeveloperApplication>::new();
+    let action_value = action.value();
+
+    Effect::watch(
+        move || action_value.get(),
+        move |action_value, _, _| {
+            if let Some(Ok(id)) = action_value {
+                toast.push_alert(AlertType::Success, "Application created successfully");
+                navigate(&format!("/developer/applications/{}", id), Default::default());
+            }
+        },
+        false,
+    );
+
+    view! {
+        <AuthenticatedPage title="Developer Applications">
+            <Suspense>
+                {move || Suspend::new(async move {
+                    match developer_applications_resource.get() {
+                        Some(Ok(applications)) if applications.is_empty() => {
+                            EitherOf4::A(view! { <div>"You have not registered any applications yet."</div> })
+                        }
+                        Some(Ok(applications)) => {
+                            EitherOf4::B(
+                                view! {
+                                    <div class="flex flex-col gap-4">
+                                        {applications
+                                            .into_iter()
+                                            .map(|application| {
+                                                view! { <DeveloperApplicationCard application=application /> }
+                                            })
+                                            .collect::<Vec<_>>()}
+                                    </div>
+                                },
+                            )
+                        }
+                        Some(Err(_)) => {
+                            EitherOf4::C(view! { <div>"You do not have access to the developer portal."</div> })
+                        }
+                        None => EitherOf4::D(()),
+                    }
+                })}
+            </Suspense>
+
+            <h2 class="h2 mt-6">"Register a New Application"</h2>
+
+            <ActionForm action=action attr:class="form" attr:autocomplete="off" attr:novalidate="true">
+                <Show when=move || action_value.read().has_errors()>
+                    <Alert alert_type=AlertType::Error>"Failed to create application"</Alert>
+                </Show>
+
+                <DeveloperApplicationFields
+                    disabled=action.pending()
+                    error=move |name| action_value.read().get_param_error(name)
+                />
+
+                <SubmitButton is_pending=action.pending() />
+            </ActionForm>
+        </AuthenticatedPage>
+    }
+}
@@ -0,0 +1,133 @@
+use leptos::either::{Either, EitherOf5};
+use leptos::prelude::*;
+use leptos_router::hooks::use_query;
+use leptos_router::params::Params;
+
+use crate::components::{ScopeList, SubmitButton, TextField};
+use crate::pages::AuthenticatedPage;
+use crate::server_fns;
+
+#[derive(Clone, Default, Params, PartialEq)]
+struct DeviceQuery {
+    user_code: Option<String>,
+}
+
+#[component]
+pub fn DevicePage() -> impl IntoView {
+    let query = use_query::<DeviceQuery>();
+    let user_code = Memo::new(move |_| query.get().unwrap_or_default().user_code.unwrap_or_default());
+    let device_authorization_resource = Resource::new_blocking(
+        move || user_code.get(),
+        async move |user_code| {
+            if user_code.is_empty() {
+                return None;
+            }
+
+            Some(server_fns::device_authorization(user_code).await)
+        },
+    );
+    let action = Action::new(move |approve: &bool| {
+        let approve = *approve;
+        async move {
+            server_fns::confirm_device_authorization(user_code.get_untracked(), approve)
+                .await
+                .map(|_| approve)
+        }
+    });
+    let action_value = action.value();
+
+    view! {
+        <AuthenticatedPage title="Connect a Device">
+            <Suspense>
+                {move || Suspend::new(async move {
+                    match (device_authorization_resource.get(), action_value.get()) {
+                        (_, Some(Ok(true))) => {
+                            EitherOf5::A(
+                                view! {
+                                    <div class="text-center">
+                                        "Device connected. You can now return to your device."
+                                    </div>
+                                },
+                            )
+                        }
+                        (_, Some(Ok(false))) => {
+                            EitherOf5::B(view! { <div class="text-center">"Device access denied."</div> })
+                        }
+                        (_, Some(Err(_))) => {
+                            EitherOf5::C(view! { <div class="text-center">"Could not connect device..."</div> })
+                        }
+                        (Some(Some(Ok(device_authorization))), None) => {
+                            EitherOf5::D(
+                                view! {
+                                    <div class="card card-border bg-base-100 max-w-160 mx-auto my-4 w-full">
+                                        <div class="card-body">
+                                            <p class="text-xl">
+                                                "Authorize "<b>{device_authorization.application.name}</b>" to:"
+                                            </p>
+
+                                            <ScopeList scopes=device_authorization.scopes />
+
+                                            <p>
+                                                "Only continue if the code "<b>{device_authorization.user_code}</b>
+                                                " is shown on your device."
+                                            </p>
+
+                                            <div class="card-actions">
+                                                <button
+                                                    on:click=move |_| {
+                                                        action.dispatch(true);
+                                                    }
+                                                    class="btn-submit"
+                                                    disabled=move || action.pending().get()
+                                                >
+                                                    {move || {
+                                                        if action.pending().get() {
+                                                            Either::Left(
+                                                                view! { <span class="loading loading-spinner" /> },
+                                                            )
+                                                        } else {
+                                                            Either::Right("Authorize")
+                                                        }
+                                                    }}
+                                                </button>
+
+                                                <button
+                                                    on:click=move |_| {
+                                                        action.dispatch(false);
+                                                    }
+                                                    class="btn btn-outline"
+                                                    disabled=move || action.pending().get()
+                                                >
+                                                    "Deny"
+                                                </button>
+                                            </div>
+                                        </div>
+                                    </div>
+                                },
+                            )
+                        }
+                        (device_authorization, _) => {
+                            EitherOf5::E(
+                                view! {
+                                    <form class="form" method="get" action="/device" autocomplete="off" novalidate>
+                                        <p>"Enter the code shown on your device."</p>
+
+                                        <TextField
+                                            label="Code"
+                                            name="user_code"
+                                            error=matches!(device_authorization, Some(Some(Err(_))))
+                                                .then(|| "Is invalid or has expired".to_owned())
+                                            value=user_code.get_untracked()
+                                        />
+
+                                        <SubmitButton label="Continue".to_owned() />
+                                    </form>
+                                },
+                            )
+                        }
+                    }
+                })}
+            </Suspense>
+        </AuthenticatedPage>
+    }
+}
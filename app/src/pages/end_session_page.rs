@@ -0,0 +1,96 @@
+use leptos::either::{Either, EitherOf4};
+use leptos::prelude::*;
+use leptos_meta::Title;
+use leptos_router::hooks::use_query;
+use leptos_router::params::Params;
+use url::Url;
+use uuid::Uuid;
+
+use crate::hooks::use_current_user_resource;
+use crate::server_fns;
+
+#[derive(Clone, Default, Params, PartialEq)]
+struct EndSessionQuery {
+    client_id: Option<Uuid>,
+    id_token_hint: Option<String>,
+    post_logout_redirect_uri: Option<Url>,
+    state: Option<String>,
+}
+
+#[component]
+pub fn EndSessionPage() -> impl IntoView {
+    let query = use_query::<EndSessionQuery>();
+    let current_user_resource = use_current_user_resource();
+    let action = Action::new(move |query: &EndSessionQuery| {
+        let query = query.to_owned();
+        async move {
+            server_fns::end_session(
+                query.client_id,
+                query.id_token_hint,
+                query.post_logout_redirect_uri,
+                query.state,
+            )
+            .await
+        }
+    });
+    let action_value = action.value();
+
+    Effect::new(move || {
+        if let Ok(query) = query.get_untracked()
+            && query.id_token_hint.is_some()
+        {
+            action.dispatch(query);
+        }
+    });
+
+    Effect::watch(
+        move || action_value.get(),
+        move |action_value, _, _| {
+            if let Some(Ok(_)) = action_value {
+                current_user_resource.refetch();
+            }
+        },
+        false,
+    );
+
+    view! {
+        <Title text="Log Out" />
+
+        <h1 class="h1">"Log Out"</h1>
+
+        {move || match action_value.get() {
+            Some(Ok(Some(_))) => EitherOf4::A(view! { <div class="text-center">"Redirecting..."</div> }),
+            Some(Ok(None)) => EitherOf4::B(view! { <div class="text-center">"You have been logged out."</div> }),
+            Some(Err(_)) => EitherOf4::C(view! { <div class="text-center">"Could not log out..."</div> }),
+            None => {
+                EitherOf4::D(
+                    view! {
+                        <div class="card card-border bg-base-100 max-w-160 mx-auto my-4 w-full">
+                            <div class="card-body">
+                                <p class="text-xl">"Are you sure you want to logout?"</p>
+
+                                <div class="card-actions">
+                                    <button
+                                        on:click=move |_| {
+                                            action.dispatch(query.get_untracked().unwrap_or_default());
+                                        }
+                                        class="btn-submit"
+                                        disabled=move || action.pending().get()
+                                    >
+                                        {move || {
+                                            if action.pending().get() {
+                                                Either::Left(view! { <span class="loading loading-spinner" /> })
+                                            } else {
+                                                Either::Right("Log out")
+                                            }
+                                        }}
+                                    </button>
+                                </div>
+                            </div>
+                        </div>
+                    },
+                )
+            }
+        }}
+    }
+}
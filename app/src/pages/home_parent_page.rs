@@ -1,7 +1,10 @@
 use leptos::prelude::*;
 use leptos_router::components::{A, Outlet};
 
-use crate::icons::{EnvelopeOutline, HomeOutline, PasswordOutline, UserOutline};
+use crate::components::CurrentUser;
+use crate::icons::{
+    CodeBracketOutline, EnvelopeOutline, HomeOutline, PasswordOutline, PuzzlePieceOutline, UserOutline,
+};
 
 #[component]
 pub fn HomeParentPage() -> impl IntoView {
@@ -40,6 +43,31 @@ pub fn HomeParentPage() -> impl IntoView {
                             <span>"Change password"</span>
                         </A>
                     </li>
+
+                    <li data-tip="Connected apps">
+                        <A href="/connected-apps">
+                            <PuzzlePieceOutline />
+
+                            <span>"Connected apps"</span>
+                        </A>
+                    </li>
+
+                    <CurrentUser children=move |result| {
+                        result
+                            .ok()
+                            .filter(|user| user.is_developer)
+                            .map(|_| {
+                                view! {
+                                    <li data-tip="Developer">
+                                        <A href="/developer/applications">
+                                            <CodeBracketOutline />
+
+                                            <span>"Developer"</span>
+                                        </A>
+                                    </li>
+                                }
+                            })
+                    } />
                 </ul>
             </div>
 
@@ -5,7 +5,7 @@ use leptos_router::hooks::use_navigate;
 use url::form_urlencoded;
 
 use crate::components::{Alert, AlertType, PasswordField, SubmitButton, TextField};
-use crate::hooks::{use_current_user_resource, use_redirect_to, use_toast};
+use crate::hooks::{use_current_user_resource, use_login_hint, use_redirect_to, use_toast};
 use crate::server_fns::{self, ActionResultExt, CreateSession};
 
 use super::GuestPage;
@@ -17,6 +17,7 @@ pub fn LoginPage() -> impl IntoView {
     let current_user_resource = use_current_user_resource();
     let mut toast = use_toast();
     let redirect_to = use_redirect_to();
+    let login_hint = use_login_hint();
     let action = ServerAction::<CreateSession>::new();
     let action_value = action.value();
     let error_username_or_email = Memo::new(move |_| action_value.read().get_param_error("username_or_email"));
@@ -46,6 +47,7 @@ pub fn LoginPage() -> impl IntoView {
                     label="Username or email"
                     name="username_or_email"
                     error=error_username_or_email
+                    value=login_hint
                 />
 
                 <PasswordField disabled=action.pending() label="Password" name="password" error=error_password />
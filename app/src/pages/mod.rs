@@ -6,21 +6,33 @@ use url::form_urlencoded;
 
 mod authorize_page;
 mod change_password_page;
+mod connected_apps_page;
+mod developer_application_page;
+mod developer_applications_page;
+mod device_page;
 mod edit_email_page;
 mod edit_profile_page;
+mod end_session_page;
 mod home_page;
 mod home_parent_page;
 mod login_page;
+mod reauthenticate_page;
 mod register_page;
 mod reset_password_page;
 
 pub use authorize_page::AuthorizePage;
 pub use change_password_page::ChangePasswordPage;
+pub use connected_apps_page::ConnectedAppsPage;
+pub use developer_application_page::DeveloperApplicationPage;
+pub use developer_applications_page::DeveloperApplicationsPage;
+pub use device_page::DevicePage;
 pub use edit_email_page::EditEmailPage;
 pub use edit_profile_page::EditProfilePage;
+pub use end_session_page::EndSessionPage;
 pub use home_page::HomePage;
 pub use home_parent_page::HomeParentPage;
 pub use login_page::LoginPage;
+pub use reauthenticate_page::ReauthenticatePage;
 pub use register_page::RegisterPage;
 pub use reset_password_page::ResetPasswordPage;
 
@@ -0,0 +1,43 @@
+use leptos::prelude::*;
+use leptos_router::hooks::use_navigate;
+
+use crate::components::{Alert, AlertType, PasswordField, SubmitButton};
+use crate::hooks::{use_redirect_to, use_toast};
+use crate::server_fns::{ActionResultExt, ReauthenticateSession};
+
+use super::AuthenticatedPage;
+
+#[component]
+pub fn ReauthenticatePage() -> impl IntoView {
+    let navigate = use_navigate();
+    let mut toast = use_toast();
+    let redirect_to = use_redirect_to();
+    let action = ServerAction::<ReauthenticateSession>::new();
+    let action_value = action.value();
+    let error_password = Memo::new(move |_| action_value.read().get_param_error("password"));
+
+    Effect::watch(
+        move || action_value.get(),
+        move |action_value, _, _| {
+            if action_value.is_success() {
+                toast.push_alert(AlertType::Success, "Identity confirmed successfully");
+                navigate(&redirect_to.get_untracked(), Default::default());
+            }
+        },
+        false,
+    );
+
+    view! {
+        <AuthenticatedPage title="Confirm Your Identity">
+            <ActionForm action=action attr:class="form" attr:autocomplete="off" attr:novalidate="true">
+                <Show when=move || action_value.read().has_errors()>
+                    <Alert alert_type=AlertType::Error>"Failed to authenticate user"</Alert>
+                </Show>
+
+                <PasswordField disabled=action.pending() label="Password" name="password" error=error_password />
+
+                <SubmitButton is_pending=action.pending() />
+            </ActionForm>
+        </AuthenticatedPage>
+    }
+}
@@ -1,16 +1,53 @@
-use chrono::NaiveDate;
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use url::Url;
 use uuid::Uuid;
 
 #[cfg(feature = "ssr")]
-use identity_core::models::{Application, User};
+use identity_core::models::{AccessToken, Application, ApplicationToken, PushedAuthorizationRequest, User};
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct AccessTokenPresenter {
+    pub id: Uuid,
+    pub scopes: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "ssr")]
+impl From<AccessToken<'_>> for AccessTokenPresenter {
+    fn from(access_token: AccessToken<'_>) -> Self {
+        AccessTokenPresenter {
+            id: access_token.id,
+            scopes: access_token.scopes,
+            created_at: access_token.created_at,
+            expires_at: access_token.expires_at,
+        }
+    }
+}
 
 #[derive(Clone, Deserialize, Serialize)]
 pub struct ApplicationPresenter {
     pub id: Uuid,
     pub name: String,
     pub is_trusted: bool,
+    pub requires_par: bool,
+    pub allowed_scopes: Vec<String>,
+    pub description: Option<String>,
+    pub homepage_url: Option<Url>,
+    pub privacy_policy_url: Option<Url>,
+    pub terms_url: Option<Url>,
+    logo_image_url: Url,
+}
+
+impl ApplicationPresenter {
+    pub fn logo_image_url(&self, size: u32) -> Url {
+        let mut logo_image_url = self.logo_image_url.clone();
+
+        logo_image_url.set_query(Some(&format!("size={}", size)));
+
+        logo_image_url
+    }
 }
 
 #[cfg(feature = "ssr")]
@@ -20,6 +57,114 @@ impl From<Application<'_>> for ApplicationPresenter {
             id: application.id,
             name: application.name.to_string(),
             is_trusted: application.is_trusted(),
+            requires_par: application.requires_par(),
+            homepage_url: application.homepage_url(),
+            privacy_policy_url: application.privacy_policy_url(),
+            terms_url: application.terms_url(),
+            logo_image_url: application.logo_image_url(),
+            allowed_scopes: application.allowed_scopes,
+            description: application.description,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ApplicationTokenPresenter {
+    pub id: Uuid,
+    pub name: String,
+    pub scopes: Vec<String>,
+    pub permissions: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "ssr")]
+impl From<ApplicationToken<'_>> for ApplicationTokenPresenter {
+    fn from(application_token: ApplicationToken<'_>) -> Self {
+        ApplicationTokenPresenter {
+            id: application_token.id,
+            name: application_token.name.to_string(),
+            scopes: application_token.scopes,
+            permissions: application_token.permissions,
+            created_at: application_token.created_at,
+            expires_at: application_token.expires_at,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct ConnectedApplicationPresenter {
+    pub authorization_id: Uuid,
+    pub application: ApplicationPresenter,
+    pub scopes: Vec<String>,
+    pub first_authorized_at: DateTime<Utc>,
+    pub last_authorized_at: DateTime<Utc>,
+    pub access_tokens: Vec<AccessTokenPresenter>,
+}
+
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct DeveloperApplicationPresenter {
+    pub id: Uuid,
+    pub name: String,
+    pub redirect_urls: Vec<String>,
+    pub post_logout_redirect_urls: Vec<String>,
+    pub is_trusted: bool,
+    pub description: Option<String>,
+    pub homepage_url: Option<String>,
+    pub privacy_policy_url: Option<String>,
+    pub terms_url: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "ssr")]
+impl From<Application<'_>> for DeveloperApplicationPresenter {
+    fn from(application: Application<'_>) -> Self {
+        DeveloperApplicationPresenter {
+            id: application.id,
+            name: application.name.to_string(),
+            is_trusted: application.is_trusted(),
+            redirect_urls: application.redirect_urls,
+            post_logout_redirect_urls: application.post_logout_redirect_urls,
+            description: application.description,
+            homepage_url: application.homepage_url,
+            privacy_policy_url: application.privacy_policy_url,
+            terms_url: application.terms_url,
+            created_at: application.created_at,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct DeviceAuthorizationPresenter {
+    pub user_code: String,
+    pub application: ApplicationPresenter,
+    pub scopes: Vec<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct PushedAuthorizationRequestPresenter {
+    pub request_uri: String,
+    pub redirect_url: Url,
+    pub scope: Option<String>,
+    pub state: Option<String>,
+    pub prompt: Option<String>,
+    pub max_age: Option<i64>,
+    pub login_hint: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "ssr")]
+impl From<PushedAuthorizationRequest<'_>> for PushedAuthorizationRequestPresenter {
+    fn from(pushed_authorization_request: PushedAuthorizationRequest<'_>) -> Self {
+        PushedAuthorizationRequestPresenter {
+            request_uri: pushed_authorization_request.request_uri.to_string(),
+            redirect_url: pushed_authorization_request.redirect_url(),
+            scope: pushed_authorization_request.scope,
+            state: pushed_authorization_request.state,
+            prompt: pushed_authorization_request.prompt,
+            max_age: pushed_authorization_request.max_age.map(i64::from),
+            login_hint: pushed_authorization_request.login_hint,
+            created_at: pushed_authorization_request.created_at,
         }
     }
 }
@@ -35,6 +180,7 @@ pub struct UserPresenter {
     pub birthdate: NaiveDate,
     pub country_code: String,
     pub initials: String,
+    pub is_developer: bool,
     avatar_image_url: Url,
 }
 
@@ -61,6 +207,7 @@ impl From<User<'_>> for UserPresenter {
             birthdate: user.birthdate,
             country_code: user.country_code.to_string(),
             initials: user.initials(),
+            is_developer: user.is_developer(),
             avatar_image_url: user.avatar_image_url(),
         }
     }
@@ -0,0 +1,208 @@
+use chrono::NaiveDate;
+use leptos::prelude::*;
+use uuid::Uuid;
+
+#[cfg(feature = "ssr")]
+use identity_core::commands;
+#[cfg(feature = "ssr")]
+use identity_core::enums::Scope;
+#[cfg(feature = "ssr")]
+use identity_core::params::{ApplicationParams, ApplicationTokenParams};
+
+use crate::presenters::{ApplicationTokenPresenter, DeveloperApplicationPresenter};
+
+use super::{ActionResult, ServerFnResult};
+
+#[cfg(feature = "ssr")]
+use super::*;
+
+#[cfg(feature = "ssr")]
+async fn extract_developer<'a>() -> ServerFnResult<User<'a>> {
+    let user = extract_user().await?;
+
+    if !user.is_developer() {
+        let resp_opts = expect_context::<ResponseOptions>();
+
+        resp_opts.set_status(StatusCode::FORBIDDEN);
+
+        return Err(ServerFnError::Request("Forbidden".to_owned()));
+    }
+
+    Ok(user)
+}
+
+#[cfg(feature = "ssr")]
+async fn extract_developer_application<'a>(application_id: Uuid) -> ServerFnResult<Application<'a>> {
+    let user = extract_developer().await?;
+    let application = commands::get_application_by_id(application_id).await?;
+
+    if !application.is_owned_by(&user) {
+        return Err(ServerFnError::Args("Invalid application".to_owned()));
+    }
+
+    Ok(application)
+}
+
+#[cfg(feature = "ssr")]
+fn non_empty(value: String) -> Option<String> {
+    let value = value.trim();
+
+    (!value.is_empty()).then(|| value.to_owned())
+}
+
+#[cfg(feature = "ssr")]
+fn split_redirect_urls(redirect_urls: &str) -> Vec<String> {
+    redirect_urls
+        .split_whitespace()
+        .map(|redirect_url| redirect_url.to_owned())
+        .collect()
+}
+
+#[server]
+pub async fn create_developer_application(
+    name: String,
+    redirect_urls: String,
+    post_logout_redirect_urls: String,
+    description: String,
+    homepage_url: String,
+    privacy_policy_url: String,
+    terms_url: String,
+) -> ActionResult<Uuid> {
+    require_authentication().await?;
+
+    let user = extract_developer().await?;
+    let application = commands::insert_application(ApplicationParams {
+        name,
+        redirect_urls: split_redirect_urls(&redirect_urls),
+        trusted: false,
+        allowed_scopes: Scope::all_strings(),
+        confidential: false,
+        jwt_access_tokens: false,
+        par_required: false,
+        backchannel_logout_url: None,
+        post_logout_redirect_urls: split_redirect_urls(&post_logout_redirect_urls),
+        description: non_empty(description),
+        homepage_url: non_empty(homepage_url),
+        privacy_policy_url: non_empty(privacy_policy_url),
+        terms_url: non_empty(terms_url),
+        logo: None,
+        owner_id: Some(user.id),
+        access_token_ttl_secs: None,
+        refresh_token_ttl_secs: None,
+        refresh_token_idle_ttl_secs: None,
+    })
+    .await?;
+
+    Ok(application.id)
+}
+
+#[server]
+pub async fn create_developer_application_token(
+    application_id: Uuid,
+    name: String,
+    expires_at: Option<NaiveDate>,
+) -> ActionResult<String> {
+    require_authentication().await?;
+
+    let application = extract_developer_application(application_id).await?;
+    let application_token = commands::insert_application_token(
+        &application,
+        ApplicationTokenParams {
+            name,
+            expires_at,
+            scopes: application.allowed_scopes.clone(),
+            permissions: Vec::new(),
+        },
+    )
+    .await?;
+
+    Ok(application_token.code.to_string())
+}
+
+#[server]
+pub async fn developer_application(id: Uuid) -> ServerFnResult<DeveloperApplicationPresenter> {
+    require_authentication().await?;
+
+    let application = extract_developer_application(id).await?;
+
+    Ok(application.into())
+}
+
+#[server]
+pub async fn developer_application_tokens(application_id: Uuid) -> ServerFnResult<Vec<ApplicationTokenPresenter>> {
+    require_authentication().await?;
+
+    let application = extract_developer_application(application_id).await?;
+    let application_tokens = commands::all_application_tokens(&application).await?;
+
+    Ok(application_tokens
+        .into_iter()
+        .map(|application_token| application_token.into())
+        .collect())
+}
+
+#[server]
+pub async fn developer_applications() -> ServerFnResult<Vec<DeveloperApplicationPresenter>> {
+    require_authentication().await?;
+
+    let user = extract_developer().await?;
+    let applications = commands::all_applications_by_owner(&user).await?;
+
+    Ok(applications.into_iter().map(|application| application.into()).collect())
+}
+
+#[server]
+pub async fn revoke_developer_application_token(id: Uuid) -> ServerFnResult {
+    require_authentication().await?;
+
+    let application_token = commands::get_application_token_by_id(id).await?;
+
+    extract_developer_application(application_token.application_id).await?;
+
+    commands::revoke_application_token(&application_token).await?;
+
+    Ok(())
+}
+
+#[server]
+pub async fn update_developer_application(
+    id: Uuid,
+    name: String,
+    redirect_urls: String,
+    post_logout_redirect_urls: String,
+    description: String,
+    homepage_url: String,
+    privacy_policy_url: String,
+    terms_url: String,
+) -> ActionResult {
+    require_authentication().await?;
+
+    let application = extract_developer_application(id).await?;
+
+    commands::update_application(
+        &application,
+        ApplicationParams {
+            name,
+            redirect_urls: split_redirect_urls(&redirect_urls),
+            trusted: application.is_trusted(),
+            allowed_scopes: application.allowed_scopes.clone(),
+            confidential: application.is_confidential(),
+            jwt_access_tokens: application.uses_jwt_access_tokens(),
+            par_required: application.requires_par(),
+            backchannel_logout_url: application.backchannel_logout_url.clone(),
+            post_logout_redirect_urls: split_redirect_urls(&post_logout_redirect_urls),
+            description: non_empty(description),
+            homepage_url: non_empty(homepage_url),
+            privacy_policy_url: non_empty(privacy_policy_url),
+            terms_url: non_empty(terms_url),
+            logo: None,
+            owner_id: application.owner_id,
+            access_token_ttl_secs: application.access_token_ttl_secs,
+            refresh_token_ttl_secs: application.refresh_token_ttl_secs,
+            refresh_token_idle_ttl_secs: application.refresh_token_idle_ttl_secs,
+        },
+    )
+    .await?;
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 #[cfg(feature = "ssr")]
 use std::net::IpAddr;
 
+use chrono::{DateTime, Utc};
 use leptos::prelude::*;
 use leptos::server_fn::codec::JsonEncoding;
 use serde::{Deserialize, Serialize};
@@ -11,6 +12,8 @@ use validator::ValidationErrors;
 #[cfg(feature = "ssr")]
 use axum_client_ip::ClientIp;
 #[cfg(feature = "ssr")]
+use chrono::TimeDelta;
+#[cfg(feature = "ssr")]
 use http::status::StatusCode;
 #[cfg(feature = "ssr")]
 use leptos_axum::{ResponseOptions, extract, redirect};
@@ -18,16 +21,21 @@ use leptos_axum::{ResponseOptions, extract, redirect};
 #[cfg(feature = "ssr")]
 use identity_core::commands;
 #[cfg(feature = "ssr")]
-use identity_core::models::{Session, User};
+use identity_core::models::{Application, Session, User};
 
-use crate::presenters::ApplicationPresenter;
+use crate::presenters::{
+    ApplicationPresenter, ConnectedApplicationPresenter, DeviceAuthorizationPresenter,
+    PushedAuthorizationRequestPresenter,
+};
 
 #[cfg(feature = "ssr")]
 use crate::constants::KEY_SESSION_ID;
 
+mod developer_server_fns;
 mod session_server_fns;
 mod user_server_fns;
 
+pub use developer_server_fns::*;
 pub use session_server_fns::*;
 pub use user_server_fns::*;
 
@@ -179,6 +187,45 @@ async fn extract_user<'a>() -> ServerFnResult<User<'a>> {
     Ok(user)
 }
 
+#[cfg(feature = "ssr")]
+async fn insert_authorization_and_redirect(
+    application: &Application<'_>,
+    redirect_url: Url,
+    code_challenge: &str,
+    scope: Option<&str>,
+    nonce: Option<&str>,
+    state: Option<&str>,
+) -> ServerFnResult<Url> {
+    let session = extract_session().await?;
+    let Some(scopes) = application.granted_scopes(scope) else {
+        let Some(error_redirect_url) = application.error_redirect_url(&redirect_url, "invalid_scope", state) else {
+            return Err(ServerFnError::Args("Invalid scope".to_owned()));
+        };
+
+        redirect(error_redirect_url.as_ref());
+
+        return Ok(error_redirect_url);
+    };
+    let authorization = commands::insert_or_refresh_authorization(
+        application,
+        &session,
+        &redirect_url,
+        code_challenge,
+        &scopes,
+        nonce,
+        state,
+    )
+    .await?;
+
+    let Some(full_redirect_url) = authorization.full_redirect_url() else {
+        return Err(ServerFnError::Args("Invalid redirect URL".to_owned()));
+    };
+
+    redirect(full_redirect_url.as_ref());
+
+    Ok(full_redirect_url)
+}
+
 #[cfg(feature = "ssr")]
 async fn is_authenticated() -> bool {
     extract_session().await.is_ok()
@@ -213,22 +260,249 @@ pub async fn application(id: Uuid) -> ServerFnResult<ApplicationPresenter> {
     Ok(application.into())
 }
 
+#[server]
+pub async fn authorization_error(
+    application_id: Uuid,
+    redirect_url: Url,
+    error: String,
+    state: Option<String>,
+) -> ServerFnResult<Url> {
+    let application = commands::get_application_by_id(application_id).await?;
+    let Some(error_redirect_url) = application.error_redirect_url(&redirect_url, &error, state.as_deref()) else {
+        return Err(ServerFnError::Args("Invalid redirect URL".to_owned()));
+    };
+
+    redirect(error_redirect_url.as_ref());
+
+    Ok(error_redirect_url)
+}
+
+#[server]
+pub async fn confirm_device_authorization(user_code: String, approve: bool) -> ServerFnResult {
+    require_authentication().await?;
+
+    let device_authorization = commands::get_pending_device_authorization_by_user_code(&user_code).await?;
+
+    if !approve {
+        commands::deny_device_authorization(&device_authorization).await?;
+
+        return Ok(());
+    }
+
+    let session = extract_session().await?;
+
+    commands::approve_device_authorization(&device_authorization, &session).await?;
+
+    Ok(())
+}
+
+#[server]
+pub async fn connected_applications() -> ServerFnResult<Vec<ConnectedApplicationPresenter>> {
+    require_authentication().await?;
+
+    let user = extract_user().await?;
+    let authorizations = commands::all_authorizations_by_user(&user).await?;
+    let mut connected_applications = Vec::with_capacity(authorizations.len());
+
+    for authorization in authorizations {
+        let application = authorization.application().await?;
+        let access_tokens = authorization
+            .access_tokens()
+            .await?
+            .into_iter()
+            .map(|access_token| access_token.into())
+            .collect();
+
+        connected_applications.push(ConnectedApplicationPresenter {
+            authorization_id: authorization.id,
+            application: application.into(),
+            first_authorized_at: authorization.created_at,
+            last_authorized_at: authorization.updated_at.unwrap_or(authorization.created_at),
+            scopes: authorization.scopes,
+            access_tokens,
+        });
+    }
+
+    Ok(connected_applications)
+}
+
 #[server]
 pub async fn create_authorization(
     application_id: Uuid,
     redirect_url: Url,
     code_challenge: String,
+    scope: Option<String>,
+    nonce: Option<String>,
+    state: Option<String>,
 ) -> ServerFnResult<Url> {
     require_authentication().await?;
 
     let application = commands::get_application_by_id(application_id).await?;
-    let session = extract_session().await?;
-    let authorization =
-        commands::insert_or_refresh_authorization(&application, &session, redirect_url, &code_challenge).await?;
 
-    let full_redirect_url = authorization.full_redirect_url();
+    if application.requires_par() {
+        return Err(ServerFnError::Args(
+            "Pushed authorization request is required".to_owned(),
+        ));
+    }
 
-    redirect(full_redirect_url.as_ref());
+    insert_authorization_and_redirect(
+        &application,
+        redirect_url,
+        &code_challenge,
+        scope.as_deref(),
+        nonce.as_deref(),
+        state.as_deref(),
+    )
+    .await
+}
 
-    Ok(full_redirect_url)
+#[server]
+pub async fn create_pushed_authorization(application_id: Uuid, request_uri: String) -> ServerFnResult<Url> {
+    require_authentication().await?;
+
+    let application = commands::get_application_by_id(application_id).await?;
+    let pushed_authorization_request = commands::get_pushed_authorization_request(application_id, &request_uri).await?;
+
+    commands::delete_pushed_authorization_request(&pushed_authorization_request).await?;
+
+    insert_authorization_and_redirect(
+        &application,
+        pushed_authorization_request.redirect_url(),
+        &pushed_authorization_request.code_challenge,
+        pushed_authorization_request.scope.as_deref(),
+        pushed_authorization_request.nonce.as_deref(),
+        pushed_authorization_request.state.as_deref(),
+    )
+    .await
+}
+
+#[server]
+pub async fn device_authorization(user_code: String) -> ServerFnResult<DeviceAuthorizationPresenter> {
+    require_authentication().await?;
+
+    let device_authorization = commands::get_pending_device_authorization_by_user_code(&user_code).await?;
+    let application = device_authorization.application().await?;
+
+    Ok(DeviceAuthorizationPresenter {
+        user_code: device_authorization.user_code.to_string(),
+        application: application.into(),
+        scopes: device_authorization.scopes,
+    })
+}
+
+#[server]
+pub async fn disconnect_application(authorization_id: Uuid) -> ServerFnResult {
+    require_authentication().await?;
+
+    let user = extract_user().await?;
+    let authorization = commands::get_authorization_by_id(authorization_id).await?;
+
+    if authorization.user_id != user.id {
+        return Err(ServerFnError::Args("Invalid authorization".to_owned()));
+    }
+
+    commands::revoke_authorization(&authorization).await?;
+
+    Ok(())
+}
+
+#[server]
+pub async fn end_session(
+    client_id: Option<Uuid>,
+    id_token_hint: Option<String>,
+    post_logout_redirect_uri: Option<Url>,
+    state: Option<String>,
+) -> ServerFnResult<Option<Url>> {
+    let id_token_claims = match id_token_hint {
+        Some(id_token_hint) => Some(
+            commands::decode_id_token_hint(&id_token_hint)
+                .ok_or_else(|| ServerFnError::Args("Invalid id_token_hint".to_owned()))?,
+        ),
+        None => None,
+    };
+    let application_id = match (client_id, &id_token_claims) {
+        (Some(client_id), Some(claims)) if client_id != claims.aud => {
+            return Err(ServerFnError::Args("client_id does not match id_token_hint".to_owned()));
+        }
+        (Some(client_id), _) => Some(client_id),
+        (None, claims) => claims.as_ref().map(|claims| claims.aud),
+    };
+    let post_logout_redirect_url = match (post_logout_redirect_uri, application_id) {
+        (Some(redirect_url), Some(application_id)) => {
+            let application = commands::get_application_by_id(application_id).await?;
+
+            Some(
+                application
+                    .post_logout_redirect_url(&redirect_url, state.as_deref())
+                    .ok_or_else(|| ServerFnError::Args("Invalid post_logout_redirect_uri".to_owned()))?,
+            )
+        }
+        (Some(_), None) => return Err(ServerFnError::Args("client_id is required".to_owned())),
+        (None, _) => None,
+    };
+
+    if let Ok(session) = extract_session().await {
+        if id_token_claims.is_some_and(|claims| claims.sub != session.user_id) {
+            return Err(ServerFnError::Args(
+                "id_token_hint does not match the current user".to_owned(),
+            ));
+        }
+
+        commands::finish_session(&session).await?;
+
+        let tower_session = extract_tower_session().await?;
+
+        tower_session.remove::<String>(KEY_SESSION_ID).await?;
+    }
+
+    if let Some(ref post_logout_redirect_url) = post_logout_redirect_url {
+        redirect(post_logout_redirect_url.as_ref());
+    }
+
+    Ok(post_logout_redirect_url)
+}
+
+#[server]
+pub async fn granted_scopes(application_id: Uuid, scope: Option<String>) -> ServerFnResult<Option<Vec<String>>> {
+    let application = commands::get_application_by_id(application_id).await?;
+
+    Ok(application.granted_scopes(scope.as_deref()))
+}
+
+#[server]
+pub async fn has_consented(application_id: Uuid, scopes: Vec<String>) -> ServerFnResult<bool> {
+    let Ok(user) = extract_user().await else {
+        return Ok(false);
+    };
+
+    let authorizations = commands::all_authorizations_by_user(&user).await?;
+
+    Ok(authorizations.iter().any(|authorization| {
+        authorization.application_id == application_id
+            && scopes.iter().all(|scope| authorization.scopes.contains(scope))
+    }))
+}
+
+#[server]
+pub async fn login_required(max_age: Option<i64>, authenticated_since: Option<DateTime<Utc>>) -> ServerFnResult<bool> {
+    let Ok(session) = extract_session().await else {
+        return Ok(true);
+    };
+
+    let max_age_exceeded =
+        max_age.is_some_and(|max_age| Utc::now() - session.authenticated_at > TimeDelta::seconds(max_age));
+    let authenticated_before =
+        authenticated_since.is_some_and(|authenticated_since| session.authenticated_at < authenticated_since);
+
+    Ok(max_age_exceeded || authenticated_before)
+}
+
+#[server]
+pub async fn pushed_authorization_request(
+    application_id: Uuid,
+    request_uri: String,
+) -> ServerFnResult<PushedAuthorizationRequestPresenter> {
+    let pushed_authorization_request = commands::get_pushed_authorization_request(application_id, &request_uri).await?;
+
+    Ok(pushed_authorization_request.into())
 }
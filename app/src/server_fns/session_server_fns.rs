@@ -48,3 +48,22 @@ pub async fn finish_session() -> ActionResult {
 
     Ok(())
 }
+
+#[server]
+pub async fn reauthenticate_session(password: String) -> ActionResult {
+    require_authentication().await?;
+
+    let user = extract_user().await?;
+
+    commands::authenticate_user(AuthenticationParams {
+        username_or_email: user.username.to_string(),
+        password,
+    })
+    .await?;
+
+    let session = extract_session().await?;
+
+    commands::reauthenticate_session(&session).await?;
+
+    Ok(())
+}
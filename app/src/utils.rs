@@ -1,5 +1,11 @@
 use std::time::Duration;
 
+use chrono::{DateTime, Utc};
+
+pub fn format_datetime(value: &DateTime<Utc>) -> String {
+    value.format("%Y-%m-%d %H:%M UTC").to_string()
+}
+
 pub async fn sleep(millis: u64) {
     let duration = Duration::from_millis(millis);
 
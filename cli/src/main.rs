@@ -1,7 +1,10 @@
-use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+use clap::{ArgAction, Parser, Subcommand};
 
 use chrono::NaiveDate;
 use identity_core::commands;
+use identity_core::enums::{Permission, Scope};
 use identity_core::models::{Application, ApplicationToken};
 use identity_core::params::{ApplicationParams, ApplicationTokenParams, UserParams};
 use uuid::Uuid;
@@ -24,10 +27,40 @@ enum CliCommand {
     CreateApplication {
         #[arg(short, long)]
         name: String,
-        #[arg(short, long)]
-        redirect_url: String,
+        #[arg(short, long = "redirect-url", required = true)]
+        redirect_urls: Vec<String>,
         #[arg(short, long)]
         trusted: Option<bool>,
+        #[arg(short, long, value_delimiter = ',')]
+        allowed_scopes: Option<Vec<String>>,
+        #[arg(short, long)]
+        confidential: Option<bool>,
+        #[arg(short, long)]
+        jwt_access_tokens: Option<bool>,
+        #[arg(short, long)]
+        par_required: Option<bool>,
+        #[arg(short, long)]
+        backchannel_logout_url: Option<String>,
+        #[arg(long = "post-logout-redirect-url")]
+        post_logout_redirect_urls: Option<Vec<String>>,
+        #[arg(long)]
+        description: Option<String>,
+        #[arg(long)]
+        homepage_url: Option<String>,
+        #[arg(long)]
+        privacy_policy_url: Option<String>,
+        #[arg(long)]
+        terms_url: Option<String>,
+        #[arg(long)]
+        logo_path: Option<PathBuf>,
+        #[arg(long)]
+        owner_id: Option<Uuid>,
+        #[arg(long)]
+        access_token_ttl_secs: Option<i32>,
+        #[arg(long)]
+        refresh_token_ttl_secs: Option<i32>,
+        #[arg(long)]
+        refresh_token_idle_ttl_secs: Option<i32>,
     },
     CreateApplicationToken {
         #[arg(short, long)]
@@ -36,6 +69,10 @@ enum CliCommand {
         name: String,
         #[arg(short, long)]
         expires_at: Option<NaiveDate>,
+        #[arg(short, long, value_delimiter = ',')]
+        scopes: Option<Vec<String>>,
+        #[arg(short, long, value_delimiter = ',')]
+        permissions: Option<Vec<String>>,
     },
     CreateUser {
         #[arg(short, long)]
@@ -55,6 +92,10 @@ enum CliCommand {
         #[arg(short, long)]
         id: Uuid,
     },
+    ResetApplicationSecret {
+        #[arg(short, long)]
+        id: Uuid,
+    },
     RevoveApplicationToken {
         #[arg(short, long)]
         id: Uuid,
@@ -64,20 +105,96 @@ enum CliCommand {
         id: Uuid,
         #[arg(short, long)]
         name: Option<String>,
-        #[arg(short, long)]
-        redirect_url: Option<String>,
+        #[arg(short, long = "redirect-url")]
+        redirect_urls: Option<Vec<String>>,
         #[arg(short, long)]
         trusted: Option<bool>,
+        #[arg(short, long, value_delimiter = ',')]
+        allowed_scopes: Option<Vec<String>>,
+        #[arg(short, long)]
+        confidential: Option<bool>,
+        #[arg(short, long)]
+        jwt_access_tokens: Option<bool>,
+        #[arg(short, long)]
+        par_required: Option<bool>,
+        #[arg(short, long)]
+        backchannel_logout_url: Option<String>,
+        #[arg(long = "post-logout-redirect-url")]
+        post_logout_redirect_urls: Option<Vec<String>>,
+        #[arg(long)]
+        description: Option<String>,
+        #[arg(long)]
+        homepage_url: Option<String>,
+        #[arg(long)]
+        privacy_policy_url: Option<String>,
+        #[arg(long)]
+        terms_url: Option<String>,
+        #[arg(long)]
+        logo_path: Option<PathBuf>,
+        #[arg(long)]
+        owner_id: Option<Uuid>,
+        #[arg(long)]
+        access_token_ttl_secs: Option<i32>,
+        #[arg(long)]
+        refresh_token_ttl_secs: Option<i32>,
+        #[arg(long)]
+        refresh_token_idle_ttl_secs: Option<i32>,
+        #[arg(long, conflicts_with = "access_token_ttl_secs")]
+        clear_access_token_ttl: bool,
+        #[arg(long, conflicts_with = "refresh_token_ttl_secs")]
+        clear_refresh_token_ttl: bool,
+        #[arg(long, conflicts_with = "refresh_token_idle_ttl_secs")]
+        clear_refresh_token_idle_ttl: bool,
+    },
+    UpdateApplicationClientCredentialsPermissions {
+        #[arg(short, long)]
+        id: Uuid,
+        #[arg(short, long, value_delimiter = ',')]
+        permissions: Option<Vec<String>>,
+    },
+    UpdateApplicationTokenPermissions {
+        #[arg(short, long)]
+        id: Uuid,
+        #[arg(short, long, value_delimiter = ',')]
+        permissions: Option<Vec<String>>,
+    },
+    UpdateUserDeveloper {
+        #[arg(short, long)]
+        username_or_id: String,
+        #[arg(short, long, action = ArgAction::Set)]
+        developer: Option<bool>,
     },
 }
 
 fn print_application(application: &Application) {
     println!(
-        "\nID: {}\nName: {}\nRedirect URL: {}\nTrusted: {}\nCreated at: {}\nUpdated at: {}",
+        "\nID: {}\nName: {}\nRedirect URLs: {}\nTrusted: {}\nAllowed scopes: {}\nConfidential: {}\nJWT access tokens: {}\nPAR required: {}\nBack-channel logout URL: {}\nPost-logout redirect URLs: {}\nDescription: {}\nHomepage URL: {}\nPrivacy policy URL: {}\nTerms URL: {}\nLogo: {}\nOwner ID: {}\nAccess token TTL: {}s\nRefresh token TTL: {}\nRefresh token idle TTL: {}s\nClient credentials permissions: {}\nCreated at: {}\nUpdated at: {}",
         application.id,
         application.name,
-        application.redirect_url,
+        application.redirect_urls.join(", "),
         application.is_trusted(),
+        application.allowed_scopes.join(", "),
+        application.is_confidential(),
+        application.uses_jwt_access_tokens(),
+        application.requires_par(),
+        application.backchannel_logout_url.as_deref().unwrap_or("None"),
+        application.post_logout_redirect_urls.join(", "),
+        application.description.as_deref().unwrap_or("None"),
+        application.homepage_url.as_deref().unwrap_or("None"),
+        application.privacy_policy_url.as_deref().unwrap_or("None"),
+        application.terms_url.as_deref().unwrap_or("None"),
+        application.has_logo(),
+        application
+            .owner_id
+            .map(|owner_id| owner_id.to_string())
+            .unwrap_or_else(|| "None".to_owned()),
+        application.access_token_ttl().as_secs(),
+        application
+            .refresh_token_ttl()
+            .map(|refresh_token_ttl| format!("{}s", refresh_token_ttl.as_secs()))
+            .unwrap_or_else(|| "None".to_owned()),
+        application.refresh_token_idle_ttl().as_secs(),
+        application.client_credentials_permissions.join(", "),
         application.created_at,
         application
             .updated_at
@@ -86,14 +203,24 @@ fn print_application(application: &Application) {
     );
 }
 
+async fn print_application_secret(application: &Application<'_>) {
+    let result = commands::reset_application_secret(application).await;
+
+    match result {
+        Ok(secret) => println!("\nClient secret (it will not be shown again): {secret}"),
+        Err(err) => println!("Failed to generate client secret.\n\n{err}"),
+    }
+}
+
 fn print_application_token(application_token: &ApplicationToken) {
     println!(
-        "\nID: {}\nApplication ID: {}\nName: {}\nCode: {}\nExpires At: {}\nCreated at: {}\nUpdated at: {}",
+        "\nID: {}\nApplication ID: {}\nName: {}\nExpires At: {}\nScopes: {}\nPermissions: {}\nCreated at: {}\nUpdated at: {}",
         application_token.id,
         application_token.application_id,
         application_token.name,
-        application_token.code,
         application_token.expires_at,
+        application_token.scopes.join(", "),
+        application_token.permissions.join(", "),
         application_token.created_at,
         application_token
             .updated_at
@@ -153,13 +280,45 @@ async fn main() {
         }
         CliCommand::CreateApplication {
             name,
-            redirect_url,
+            redirect_urls,
             trusted,
+            allowed_scopes,
+            confidential,
+            jwt_access_tokens,
+            par_required,
+            backchannel_logout_url,
+            post_logout_redirect_urls,
+            description,
+            homepage_url,
+            privacy_policy_url,
+            terms_url,
+            logo_path,
+            owner_id,
+            access_token_ttl_secs,
+            refresh_token_ttl_secs,
+            refresh_token_idle_ttl_secs,
         } => {
             let result = commands::insert_application(ApplicationParams {
                 name: name.clone(),
-                redirect_url: redirect_url.clone(),
+                redirect_urls: redirect_urls.clone(),
                 trusted: trusted.unwrap_or(false),
+                allowed_scopes: allowed_scopes.clone().unwrap_or_else(Scope::all_strings),
+                confidential: confidential.unwrap_or(false),
+                jwt_access_tokens: jwt_access_tokens.unwrap_or(false),
+                par_required: par_required.unwrap_or(false),
+                backchannel_logout_url: backchannel_logout_url.clone(),
+                post_logout_redirect_urls: post_logout_redirect_urls.clone().unwrap_or_default(),
+                description: description.clone(),
+                homepage_url: homepage_url.clone(),
+                privacy_policy_url: privacy_policy_url.clone(),
+                terms_url: terms_url.clone(),
+                logo: logo_path
+                    .as_ref()
+                    .map(|logo_path| std::fs::read(logo_path).expect("Could not read logo")),
+                owner_id: *owner_id,
+                access_token_ttl_secs: *access_token_ttl_secs,
+                refresh_token_ttl_secs: *refresh_token_ttl_secs,
+                refresh_token_idle_ttl_secs: *refresh_token_idle_ttl_secs,
             })
             .await;
 
@@ -167,6 +326,10 @@ async fn main() {
                 Ok(application) => {
                     println!("Application created successfully.");
                     print_application(&application);
+
+                    if application.is_confidential() {
+                        print_application_secret(&application).await;
+                    }
                 }
                 Err(err) => println!("Failed to create application.\n\n{err}"),
             }
@@ -175,6 +338,8 @@ async fn main() {
             application_id,
             name,
             expires_at,
+            scopes,
+            permissions,
         } => {
             let application = commands::get_application_by_id(*application_id)
                 .await
@@ -185,6 +350,8 @@ async fn main() {
                 ApplicationTokenParams {
                     name: name.clone(),
                     expires_at: *expires_at,
+                    scopes: scopes.clone().unwrap_or(application.allowed_scopes.clone()),
+                    permissions: permissions.clone().unwrap_or_default(),
                 },
             )
             .await;
@@ -193,6 +360,7 @@ async fn main() {
                 Ok(application_token) => {
                     println!("Application token created successfully.");
                     print_application_token(&application_token);
+                    println!("\nCode (it will not be shown again): {}", application_token.code);
                 }
                 Err(err) => println!("Failed to create application token.\n\n{err}"),
             }
@@ -231,6 +399,13 @@ async fn main() {
                 Err(err) => println!("Failed to delete application.\n\n{err}"),
             }
         }
+        CliCommand::ResetApplicationSecret { id } => {
+            let application = commands::get_application_by_id(*id)
+                .await
+                .expect("Could not get application");
+
+            print_application_secret(&application).await;
+        }
         CliCommand::RevoveApplicationToken { id } => {
             let application_token = commands::get_application_token_by_id(*id)
                 .await
@@ -245,10 +420,49 @@ async fn main() {
         CliCommand::UpdateApplication {
             id,
             name,
-            redirect_url,
+            redirect_urls,
             trusted,
+            allowed_scopes,
+            confidential,
+            jwt_access_tokens,
+            par_required,
+            backchannel_logout_url,
+            post_logout_redirect_urls,
+            description,
+            homepage_url,
+            privacy_policy_url,
+            terms_url,
+            logo_path,
+            owner_id,
+            access_token_ttl_secs,
+            refresh_token_ttl_secs,
+            refresh_token_idle_ttl_secs,
+            clear_access_token_ttl,
+            clear_refresh_token_ttl,
+            clear_refresh_token_idle_ttl,
         } => {
-            if name.is_none() && redirect_url.is_none() && trusted.is_none() {
+            if name.is_none()
+                && redirect_urls.is_none()
+                && trusted.is_none()
+                && allowed_scopes.is_none()
+                && confidential.is_none()
+                && jwt_access_tokens.is_none()
+                && par_required.is_none()
+                && backchannel_logout_url.is_none()
+                && post_logout_redirect_urls.is_none()
+                && description.is_none()
+                && homepage_url.is_none()
+                && privacy_policy_url.is_none()
+                && terms_url.is_none()
+                && logo_path.is_none()
+                && owner_id.is_none()
+                && access_token_ttl_secs.is_none()
+                && refresh_token_ttl_secs.is_none()
+                && refresh_token_idle_ttl_secs.is_none()
+                && !clear_access_token_ttl
+                && !clear_refresh_token_ttl
+                && !clear_refresh_token_idle_ttl
+            {
                 println!("No changes to update.");
                 return;
             }
@@ -260,8 +474,35 @@ async fn main() {
                 &application,
                 ApplicationParams {
                     name: name.clone().unwrap_or(application.name.to_string()),
-                    redirect_url: redirect_url.clone().unwrap_or(application.redirect_url.to_string()),
+                    redirect_urls: redirect_urls.clone().unwrap_or(application.redirect_urls.clone()),
                     trusted: trusted.unwrap_or(application.is_trusted()),
+                    allowed_scopes: allowed_scopes.clone().unwrap_or(application.allowed_scopes.clone()),
+                    confidential: confidential.unwrap_or(application.is_confidential()),
+                    jwt_access_tokens: jwt_access_tokens.unwrap_or(application.uses_jwt_access_tokens()),
+                    par_required: par_required.unwrap_or(application.requires_par()),
+                    backchannel_logout_url: backchannel_logout_url
+                        .clone()
+                        .or(application.backchannel_logout_url.clone()),
+                    post_logout_redirect_urls: post_logout_redirect_urls
+                        .clone()
+                        .unwrap_or(application.post_logout_redirect_urls.clone()),
+                    description: description.clone().or(application.description.clone()),
+                    homepage_url: homepage_url.clone().or(application.homepage_url.clone()),
+                    privacy_policy_url: privacy_policy_url.clone().or(application.privacy_policy_url.clone()),
+                    terms_url: terms_url.clone().or(application.terms_url.clone()),
+                    logo: logo_path
+                        .as_ref()
+                        .map(|logo_path| std::fs::read(logo_path).expect("Could not read logo")),
+                    owner_id: owner_id.or(application.owner_id),
+                    access_token_ttl_secs: access_token_ttl_secs
+                        .or(application.access_token_ttl_secs)
+                        .filter(|_| !clear_access_token_ttl),
+                    refresh_token_ttl_secs: refresh_token_ttl_secs
+                        .or(application.refresh_token_ttl_secs)
+                        .filter(|_| !clear_refresh_token_ttl),
+                    refresh_token_idle_ttl_secs: refresh_token_idle_ttl_secs
+                        .or(application.refresh_token_idle_ttl_secs)
+                        .filter(|_| !clear_refresh_token_idle_ttl),
                 },
             )
             .await;
@@ -274,5 +515,60 @@ async fn main() {
                 Err(err) => println!("Failed to update application.\n\n{err}"),
             }
         }
+        CliCommand::UpdateApplicationClientCredentialsPermissions { id, permissions } => {
+            let permissions = permissions
+                .clone()
+                .unwrap_or_default()
+                .iter()
+                .map(|permission| permission.parse::<Permission>())
+                .collect::<Result<Vec<_>, _>>()
+                .expect("Invalid permission");
+            let application = commands::get_application_by_id(*id)
+                .await
+                .expect("Could not get application");
+            let result = commands::update_application_client_credentials_permissions(&application, &permissions).await;
+
+            match result {
+                Ok(_) => println!("Application updated successfully."),
+                Err(err) => println!("Failed to update application.\n\n{err}"),
+            }
+        }
+        CliCommand::UpdateApplicationTokenPermissions { id, permissions } => {
+            let permissions = permissions
+                .clone()
+                .unwrap_or_default()
+                .iter()
+                .map(|permission| permission.parse::<Permission>())
+                .collect::<Result<Vec<_>, _>>()
+                .expect("Invalid permission");
+            let application_token = commands::get_application_token_by_id(*id)
+                .await
+                .expect("Could not get application token");
+            let result = commands::update_application_token_permissions(&application_token, &permissions).await;
+
+            match result {
+                Ok(_) => println!("Application token updated successfully."),
+                Err(err) => println!("Failed to update application token.\n\n{err}"),
+            }
+        }
+        CliCommand::UpdateUserDeveloper {
+            username_or_id,
+            developer,
+        } => {
+            let Some(developer) = developer else {
+                println!("No changes to update.");
+                return;
+            };
+
+            let user = commands::get_user_by_username_or_id(username_or_id)
+                .await
+                .expect("Could not get user");
+            let result = commands::update_user_developer(&user, *developer).await;
+
+            match result {
+                Ok(_) => println!("User updated successfully."),
+                Err(err) => println!("Failed to update user.\n\n{err}"),
+            }
+        }
     }
 }
@@ -0,0 +1,45 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub const BACKCHANNEL_LOGOUT_EVENT: &str = "http://schemas.openid.net/event/backchannel-logout";
+
+#[derive(Deserialize, Serialize)]
+pub struct AccessTokenClaims {
+    pub iss: String,
+    pub sub: Uuid,
+    pub aud: String,
+    pub client_id: Uuid,
+    pub scope: String,
+    pub sid: Uuid,
+    pub exp: i64,
+    pub iat: i64,
+    pub jti: String,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct IdTokenClaims {
+    pub iss: String,
+    pub sub: Uuid,
+    pub aud: Uuid,
+    pub exp: i64,
+    pub iat: i64,
+    pub auth_time: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sid: Option<Uuid>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct LogoutTokenClaims {
+    pub iss: String,
+    pub sub: Uuid,
+    pub aud: Uuid,
+    pub iat: i64,
+    pub exp: i64,
+    pub jti: String,
+    pub sid: Uuid,
+    pub events: HashMap<String, HashMap<String, String>>,
+}
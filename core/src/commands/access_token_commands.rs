@@ -1,16 +1,56 @@
 use cached::AsyncRedisCache;
 use cached::proc_macro::io_cached;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
 
 use toolbox::cache::{AsyncRedisCacheExt, redis_cache_store};
 use toolbox::rand::random_string;
 
-use crate::config::ACCESS_TOKEN_CONFIG;
+use crate::claims::AccessTokenClaims;
+use crate::config::{ACCESS_TOKEN_CONFIG, API_CONFIG};
 use crate::constants::*;
 use crate::db_pool;
+use crate::enums::SecurityEventKind;
 use crate::models::{AccessToken, Application, Authorization, Session};
 
-use super::{GET_USER_BY_ACCESS_TOKEN_CODE, refresh_session};
+use super::{digest_code, encode_jwt, insert_security_event, refresh_session};
+
+fn encode_access_token(
+    application: &Application<'_>,
+    session: &Session,
+    scopes: &[String],
+    expires_at: DateTime<Utc>,
+) -> jsonwebtoken::errors::Result<String> {
+    let claims = AccessTokenClaims {
+        iss: API_CONFIG.issuer(),
+        sub: session.user_id,
+        aud: API_CONFIG.issuer(),
+        client_id: application.id,
+        scope: scopes.join(" "),
+        sid: session.id,
+        exp: expires_at.timestamp(),
+        iat: Utc::now().timestamp(),
+        jti: random_string(32..=32),
+    };
+
+    encode_jwt(&claims, "at+jwt")
+}
+
+pub async fn all_access_tokens_by_authorization<'a>(
+    authorization: &Authorization<'_>,
+) -> sqlx::Result<Vec<AccessToken<'a>>> {
+    let db_pool = db_pool().await;
+
+    sqlx::query_as!(
+        AccessToken,
+        "SELECT * FROM access_tokens
+        WHERE authorization_id = $1 AND expires_at > current_timestamp AND revoked_at IS NULL
+        ORDER BY created_at DESC",
+        authorization.id // $1
+    )
+    .fetch_all(db_pool)
+    .await
+}
 
 pub async fn all_access_tokens_by_session(session: &Session) -> sqlx::Result<Vec<AccessToken<'_>>> {
     let db_pool = db_pool().await;
@@ -25,16 +65,20 @@ pub async fn all_access_tokens_by_session(session: &Session) -> sqlx::Result<Vec
     .await
 }
 
-#[io_cached(
-    map_error = r##"|_| sqlx::Error::RowNotFound"##,
-    ty = "AsyncRedisCache<String, AccessToken<'_>>",
-    create = r##"{ redis_cache_store(CACHE_PREFIX_GET_ACCESS_TOKEN_BY_CODE).await }"##
-)]
-pub async fn get_access_token_by_code(code: String) -> sqlx::Result<AccessToken<'static>> {
+pub async fn get_access_token_by_code(code: &str) -> sqlx::Result<AccessToken<'static>> {
     if code.is_empty() {
         return Err(sqlx::Error::RowNotFound);
     }
 
+    get_access_token_by_code_digest(digest_code(code)).await
+}
+
+#[io_cached(
+    map_error = r##"|_| sqlx::Error::RowNotFound"##,
+    ty = "AsyncRedisCache<String, AccessToken<'_>>",
+    create = r##"{ redis_cache_store(CACHE_PREFIX_GET_ACCESS_TOKEN_BY_CODE_DIGEST).await }"##
+)]
+async fn get_access_token_by_code_digest(code_digest: String) -> sqlx::Result<AccessToken<'static>> {
     let db_pool = db_pool().await;
 
     sqlx::query_as!(
@@ -42,18 +86,40 @@ pub async fn get_access_token_by_code(code: String) -> sqlx::Result<AccessToken<
         "SELECT * FROM access_tokens
         WHERE code = $1 AND code_expires_at > current_timestamp AND revoked_at IS NULL
         LIMIT 1",
-        code // $1
+        code_digest // $1
     )
     .fetch_one(db_pool)
     .await
 }
 
+pub async fn get_access_token_by_refresh_code(refresh_code: &str) -> sqlx::Result<AccessToken<'static>> {
+    if refresh_code.is_empty() {
+        return Err(sqlx::Error::RowNotFound);
+    }
+
+    get_access_token_by_refresh_code_digest(digest_code(refresh_code)).await
+}
+
 #[io_cached(
     map_error = r##"|_| sqlx::Error::RowNotFound"##,
     ty = "AsyncRedisCache<String, AccessToken<'_>>",
-    create = r##"{ redis_cache_store(CACHE_PREFIX_GET_ACCESS_TOKEN_BY_REFRESH_CODE).await }"##
+    create = r##"{ redis_cache_store(CACHE_PREFIX_GET_ACCESS_TOKEN_BY_REFRESH_CODE_DIGEST).await }"##
 )]
-pub async fn get_access_token_by_refresh_code(refresh_code: String) -> sqlx::Result<AccessToken<'static>> {
+async fn get_access_token_by_refresh_code_digest(refresh_code_digest: String) -> sqlx::Result<AccessToken<'static>> {
+    let db_pool = db_pool().await;
+
+    sqlx::query_as!(
+        AccessToken,
+        "SELECT * FROM access_tokens
+        WHERE refresh_code = $1 AND expires_at > current_timestamp AND revoked_at IS NULL
+        LIMIT 1",
+        refresh_code_digest // $1
+    )
+    .fetch_one(db_pool)
+    .await
+}
+
+pub async fn get_refreshed_access_token_by_refresh_code<'a>(refresh_code: &str) -> sqlx::Result<AccessToken<'a>> {
     if refresh_code.is_empty() {
         return Err(sqlx::Error::RowNotFound);
     }
@@ -62,10 +128,8 @@ pub async fn get_access_token_by_refresh_code(refresh_code: String) -> sqlx::Res
 
     sqlx::query_as!(
         AccessToken,
-        "SELECT * FROM access_tokens
-        WHERE refresh_code = $1 AND expires_at > current_timestamp AND revoked_at IS NULL
-        LIMIT 1",
-        refresh_code // $1
+        "SELECT * FROM access_tokens WHERE refresh_code = $1 AND refreshed_at IS NOT NULL LIMIT 1",
+        digest_code(refresh_code) // $1
     )
     .fetch_one(db_pool)
     .await
@@ -75,6 +139,7 @@ pub async fn insert_access_token<'a>(
     application: &Application<'_>,
     authorization: &Authorization<'_>,
     session: &Session,
+    refreshed_access_token: Option<&AccessToken<'_>>,
 ) -> sqlx::Result<AccessToken<'a>> {
     if authorization.user_id != session.user_id {
         return Err(sqlx::Error::InvalidArgument("Invalid authorization".to_owned()));
@@ -83,29 +148,52 @@ pub async fn insert_access_token<'a>(
     let db_pool = db_pool().await;
 
     let user = session.user().await?;
-    let code = random_string(ACCESS_TOKEN_CONFIG.length());
+    let code_expires_at = Utc::now() + application.access_token_ttl();
+    let code = if application.uses_jwt_access_tokens() {
+        encode_access_token(application, session, &authorization.scopes, code_expires_at)
+            .map_err(|error| sqlx::Error::Encode(Box::new(error)))?
+    } else {
+        random_string(ACCESS_TOKEN_CONFIG.length())
+    };
     let refresh_code = random_string(ACCESS_TOKEN_CONFIG.length());
-    let code_expires_at = Utc::now() + ACCESS_TOKEN_CONFIG.code_ttl();
-    let expires_at = Utc::now() + ACCESS_TOKEN_CONFIG.ttl();
+    let refresh_started_at = refreshed_access_token
+        .map(|access_token| access_token.refresh_started_at)
+        .unwrap_or_else(Utc::now);
+    let authorization_code = match refreshed_access_token {
+        Some(access_token) => access_token.authorization_code.clone(),
+        None => Some(authorization.code.to_string()),
+    };
+    let idle_expires_at = Utc::now() + application.refresh_token_idle_ttl();
+    let expires_at = application
+        .refresh_token_ttl()
+        .map(|refresh_token_ttl| idle_expires_at.min(refresh_started_at + refresh_token_ttl))
+        .unwrap_or(idle_expires_at);
 
-    let access_token = sqlx::query_as!(
+    let mut access_token = sqlx::query_as!(
         AccessToken,
         "INSERT INTO access_tokens (
-            application_id, authorization_id, session_id, user_id, code, refresh_code, code_expires_at, expires_at
+            application_id, authorization_id, session_id, user_id, code, refresh_code, code_expires_at, expires_at,
+            scopes, refresh_started_at, authorization_code
         )
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8) RETURNING *",
-        application.id,   // $1
-        authorization.id, // $2
-        session.id,       // $3
-        user.id,          // $4
-        code,             // $5
-        refresh_code,     // $6
-        code_expires_at,  // $7
-        expires_at,       // $8
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11) RETURNING *",
+        application.id,             // $1
+        authorization.id,           // $2
+        session.id,                 // $3
+        user.id,                    // $4
+        digest_code(&code),         // $5
+        digest_code(&refresh_code), // $6
+        code_expires_at,            // $7
+        expires_at,                 // $8
+        &authorization.scopes,      // $9
+        refresh_started_at,         // $10
+        authorization_code,         // $11
     )
     .fetch_one(db_pool)
     .await?;
 
+    access_token.code = code.into();
+    access_token.refresh_code = refresh_code.into();
+
     if session.should_refresh() {
         let _ = refresh_session(session).await;
     }
@@ -113,6 +201,28 @@ pub async fn insert_access_token<'a>(
     Ok(access_token)
 }
 
+pub async fn refresh_access_token(access_token: &AccessToken<'_>) -> sqlx::Result<bool> {
+    let db_pool = db_pool().await;
+
+    let refreshed_access_token = sqlx::query_as!(
+        AccessToken,
+        "UPDATE access_tokens SET refreshed_at = current_timestamp, revoked_at = current_timestamp
+        WHERE id = $1 AND refreshed_at IS NULL AND revoked_at IS NULL
+        RETURNING *",
+        access_token.id // $1
+    )
+    .fetch_optional(db_pool)
+    .await?;
+
+    let Some(refreshed_access_token) = refreshed_access_token else {
+        return Ok(false);
+    };
+
+    remove_access_token_cache(&refreshed_access_token).await;
+
+    Ok(true)
+}
+
 pub async fn revoke_access_token(access_token: &AccessToken<'_>) -> sqlx::Result<()> {
     if access_token.revoked_at.is_some() {
         return Ok(());
@@ -120,14 +230,69 @@ pub async fn revoke_access_token(access_token: &AccessToken<'_>) -> sqlx::Result
 
     let db_pool = db_pool().await;
 
-    sqlx::query!(
-        "UPDATE access_tokens SET revoked_at = current_timestamp WHERE id = $1",
+    let revoked_access_token = sqlx::query_as!(
+        AccessToken,
+        "UPDATE access_tokens SET revoked_at = current_timestamp WHERE id = $1 RETURNING *",
         access_token.id // $1
     )
-    .execute(db_pool)
+    .fetch_one(db_pool)
+    .await?;
+
+    remove_access_token_cache(&revoked_access_token).await;
+
+    Ok(())
+}
+
+pub async fn revoke_access_tokens_by_authorization_code(authorization_code: &str) -> sqlx::Result<()> {
+    let db_pool = db_pool().await;
+
+    let access_tokens = sqlx::query_as!(
+        AccessToken,
+        "UPDATE access_tokens SET revoked_at = current_timestamp
+        WHERE authorization_code = $1 AND revoked_at IS NULL
+        RETURNING *",
+        authorization_code // $1
+    )
+    .fetch_all(db_pool)
     .await?;
 
-    remove_access_token_cache(access_token).await;
+    for access_token in &access_tokens {
+        remove_access_token_cache(access_token).await;
+    }
+
+    Ok(())
+}
+
+pub async fn revoke_access_tokens_by_authorization_id(authorization_id: Uuid) -> sqlx::Result<()> {
+    let db_pool = db_pool().await;
+
+    let access_tokens = sqlx::query_as!(
+        AccessToken,
+        "UPDATE access_tokens SET revoked_at = current_timestamp
+        WHERE authorization_id = $1 AND revoked_at IS NULL
+        RETURNING *",
+        authorization_id // $1
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    for access_token in &access_tokens {
+        remove_access_token_cache(access_token).await;
+    }
+
+    Ok(())
+}
+
+pub async fn revoke_reused_access_token(access_token: &AccessToken<'_>) -> sqlx::Result<()> {
+    revoke_access_tokens_by_authorization_id(access_token.authorization_id).await?;
+
+    insert_security_event(
+        SecurityEventKind::RefreshTokenReused,
+        access_token.user_id,
+        Some(access_token.application_id),
+        Some(access_token.authorization_id),
+    )
+    .await?;
 
     Ok(())
 }
@@ -137,8 +302,8 @@ async fn remove_access_token_cache(access_token: &AccessToken<'_>) {
     let refresh_code = access_token.refresh_code.to_string();
 
     tokio::join!(
-        GET_ACCESS_TOKEN_BY_CODE.cache_remove(CACHE_PREFIX_GET_ACCESS_TOKEN_BY_CODE, &code),
-        GET_ACCESS_TOKEN_BY_REFRESH_CODE.cache_remove(CACHE_PREFIX_GET_ACCESS_TOKEN_BY_REFRESH_CODE, &refresh_code),
-        GET_USER_BY_ACCESS_TOKEN_CODE.cache_remove(CACHE_PREFIX_GET_USER_BY_ACCESS_TOKEN_CODE, &code)
+        GET_ACCESS_TOKEN_BY_CODE_DIGEST.cache_remove(CACHE_PREFIX_GET_ACCESS_TOKEN_BY_CODE_DIGEST, &code),
+        GET_ACCESS_TOKEN_BY_REFRESH_CODE_DIGEST
+            .cache_remove(CACHE_PREFIX_GET_ACCESS_TOKEN_BY_REFRESH_CODE_DIGEST, &refresh_code)
     );
 }
@@ -1,16 +1,22 @@
 use cached::AsyncRedisCache;
 use cached::proc_macro::io_cached;
 use uuid::Uuid;
-use validator::Validate;
+use validator::{Validate, ValidationErrors};
 
 use toolbox::cache::{AsyncRedisCacheExt, redis_cache_store};
+use toolbox::constants::ERROR_IS_INVALID;
+use toolbox::rand::random_string;
 use toolbox::validator::{OrValidationErrors, ValidationResult};
 
+use crate::config::{APPLICATION_CONFIG, STORAGE_CONFIG};
 use crate::constants::CACHE_PREFIX_GET_APPLICATION_BY_ID;
 use crate::db_pool;
-use crate::models::Application;
+use crate::enums::Permission;
+use crate::models::{Application, Session, User};
 use crate::params::ApplicationParams;
 
+use super::encrypt_password;
+
 pub async fn all_applications<'a>() -> sqlx::Result<Vec<Application<'a>>> {
     let db_pool = db_pool().await;
 
@@ -19,6 +25,35 @@ pub async fn all_applications<'a>() -> sqlx::Result<Vec<Application<'a>>> {
         .await
 }
 
+pub async fn all_applications_by_owner<'a>(owner: &User<'_>) -> sqlx::Result<Vec<Application<'a>>> {
+    let db_pool = db_pool().await;
+
+    sqlx::query_as!(
+        Application,
+        "SELECT * FROM applications WHERE owner_id = $1 ORDER BY created_at DESC",
+        owner.id // $1
+    )
+    .fetch_all(db_pool)
+    .await
+}
+
+pub async fn all_applications_with_backchannel_logout_by_session<'a>(
+    session: &Session,
+) -> sqlx::Result<Vec<Application<'a>>> {
+    let db_pool = db_pool().await;
+
+    sqlx::query_as!(
+        Application,
+        "SELECT * FROM applications
+        WHERE
+            backchannel_logout_url IS NOT NULL
+            AND id IN (SELECT application_id FROM access_tokens WHERE session_id = $1)",
+        session.id // $1
+    )
+    .fetch_all(db_pool)
+    .await
+}
+
 pub async fn delete_application(application: Application<'_>) -> sqlx::Result<()> {
     let db_pool = db_pool().await;
 
@@ -51,17 +86,60 @@ pub async fn insert_application<'a>(params: ApplicationParams) -> ValidationResu
 
     let application = sqlx::query_as!(
         Application,
-        "INSERT INTO applications (name, redirect_url, trusted_at)
-        VALUES ($1, $2, (CASE WHEN $3 IS TRUE THEN current_timestamp ELSE NULL END))
+        "INSERT INTO applications (
+            name, redirect_urls, trusted_at, allowed_scopes, confidential_at, jwt_access_tokens_at, par_required_at,
+            backchannel_logout_url, description, homepage_url, privacy_policy_url, terms_url, logo_updated_at,
+            owner_id, access_token_ttl_secs, refresh_token_ttl_secs, refresh_token_idle_ttl_secs,
+            post_logout_redirect_urls
+        )
+        VALUES (
+            $1,
+            $2,
+            (CASE WHEN $3 IS TRUE THEN current_timestamp ELSE NULL END),
+            $4,
+            (CASE WHEN $5 IS TRUE THEN current_timestamp ELSE NULL END),
+            (CASE WHEN $6 IS TRUE THEN current_timestamp ELSE NULL END),
+            (CASE WHEN $7 IS TRUE THEN current_timestamp ELSE NULL END),
+            $8,
+            $9,
+            $10,
+            $11,
+            $12,
+            (CASE WHEN $13 IS TRUE THEN current_timestamp ELSE NULL END),
+            $14,
+            $15,
+            $16,
+            $17,
+            $18
+        )
         RETURNING *",
-        params.name,         // $1
-        params.redirect_url, // $2
-        params.trusted,      // $3
+        params.name,                        // $1
+        &params.redirect_urls,              // $2
+        params.trusted,                     // $3
+        &params.allowed_scopes,             // $4
+        params.confidential,                // $5
+        params.jwt_access_tokens,           // $6
+        params.par_required,                // $7
+        params.backchannel_logout_url,      // $8
+        params.description,                 // $9
+        params.homepage_url,                // $10
+        params.privacy_policy_url,          // $11
+        params.terms_url,                   // $12
+        params.logo.is_some(),              // $13
+        params.owner_id,                    // $14
+        params.access_token_ttl_secs,       // $15
+        params.refresh_token_ttl_secs,      // $16
+        params.refresh_token_idle_ttl_secs, // $17
+        &params.post_logout_redirect_urls,  // $18
     )
     .fetch_one(db_pool)
     .await
     .or_validation_errors()?;
 
+    if let Some(logo) = params.logo {
+        store_application_logo_image(&application, &logo)?;
+    }
+
     Ok(application)
 }
 
@@ -71,6 +149,57 @@ pub async fn remove_application_cache(application: &Application<'_>) {
         .await;
 }
 
+pub async fn reset_application_secret(application: &Application<'_>) -> sqlx::Result<String> {
+    let db_pool = db_pool().await;
+
+    let secret = random_string(APPLICATION_CONFIG.secret_length..=APPLICATION_CONFIG.secret_length);
+
+    let application = sqlx::query_as!(
+        Application,
+        "UPDATE applications SET encrypted_secret = $2 WHERE id = $1 RETURNING *",
+        application.id,            // $1
+        encrypt_password(&secret), // $2
+    )
+    .fetch_one(db_pool)
+    .await?;
+
+    remove_application_cache(&application).await;
+
+    Ok(secret)
+}
+
+fn save_application_logo_image(application: &Application<'_>, logo: &[u8]) -> anyhow::Result<()> {
+    let original_logo_image_path = application.original_logo_image_path();
+
+    std::fs::create_dir_all(
+        original_logo_image_path
+            .parent()
+            .ok_or_else(|| anyhow::anyhow!("Failed to create directory"))?,
+    )?;
+
+    image::load_from_memory(logo)?.save(&original_logo_image_path)?;
+
+    for entry in std::fs::read_dir(STORAGE_CONFIG.path.join("application_logo_images"))?.flatten() {
+        let logo_image_path = entry.path().join(format!("{}.png", application.id));
+
+        if entry.file_name() != "original" && logo_image_path.exists() {
+            std::fs::remove_file(logo_image_path)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn store_application_logo_image(application: &Application<'_>, logo: &[u8]) -> ValidationResult<()> {
+    save_application_logo_image(application, logo).map_err(|_| {
+        let mut errors = ValidationErrors::new();
+
+        errors.add("logo", ERROR_IS_INVALID.clone());
+
+        errors
+    })
+}
+
 pub async fn update_application<'a>(
     application: &Application<'a>,
     params: ApplicationParams,
@@ -84,23 +213,90 @@ pub async fn update_application<'a>(
         "UPDATE applications
         SET
             name = $2,
-            redirect_url = $3,
+            redirect_urls = $3,
             trusted_at = CASE
                 WHEN $4 IS TRUE AND trusted_at IS NOT NULL THEN trusted_at
                 WHEN $4 IS TRUE THEN current_timestamp
-                ELSE NULL END
+                ELSE NULL END,
+            allowed_scopes = $5,
+            confidential_at = CASE
+                WHEN $6 IS TRUE AND confidential_at IS NOT NULL THEN confidential_at
+                WHEN $6 IS TRUE THEN current_timestamp
+                ELSE NULL END,
+            jwt_access_tokens_at = CASE
+                WHEN $7 IS TRUE AND jwt_access_tokens_at IS NOT NULL THEN jwt_access_tokens_at
+                WHEN $7 IS TRUE THEN current_timestamp
+                ELSE NULL END,
+            par_required_at = CASE
+                WHEN $8 IS TRUE AND par_required_at IS NOT NULL THEN par_required_at
+                WHEN $8 IS TRUE THEN current_timestamp
+                ELSE NULL END,
+            backchannel_logout_url = $9,
+            description = $10,
+            homepage_url = $11,
+            privacy_policy_url = $12,
+            terms_url = $13,
+            logo_updated_at = CASE WHEN $14 IS TRUE THEN current_timestamp ELSE logo_updated_at END,
+            owner_id = $15,
+            access_token_ttl_secs = $16,
+            refresh_token_ttl_secs = $17,
+            refresh_token_idle_ttl_secs = $18,
+            post_logout_redirect_urls = $19
         WHERE id = $1
         RETURNING *",
-        application.id,      // $1
-        params.name,         // $2
-        params.redirect_url, // $3
-        params.trusted,      // $4
+        application.id,                     // $1
+        params.name,                        // $2
+        &params.redirect_urls,              // $3
+        params.trusted,                     // $4
+        &params.allowed_scopes,             // $5
+        params.confidential,                // $6
+        params.jwt_access_tokens,           // $7
+        params.par_required,                // $8
+        params.backchannel_logout_url,      // $9
+        params.description,                 // $10
+        params.homepage_url,                // $11
+        params.privacy_policy_url,          // $12
+        params.terms_url,                   // $13
+        params.logo.is_some(),              // $14
+        params.owner_id,                    // $15
+        params.access_token_ttl_secs,       // $16
+        params.refresh_token_ttl_secs,      // $17
+        params.refresh_token_idle_ttl_secs, // $18
+        &params.post_logout_redirect_urls,  // $19
     )
     .fetch_one(db_pool)
     .await
     .or_validation_errors()?;
 
+    if let Some(logo) = params.logo {
+        store_application_logo_image(&application, &logo)?;
+    }
+
     remove_application_cache(&application).await;
 
     Ok(application)
 }
+
+pub async fn update_application_client_credentials_permissions(
+    application: &Application<'_>,
+    permissions: &[Permission],
+) -> sqlx::Result<()> {
+    let db_pool = db_pool().await;
+
+    let permissions = permissions
+        .iter()
+        .map(|permission| permission.to_string())
+        .collect::<Vec<_>>();
+
+    sqlx::query!(
+        "UPDATE applications SET client_credentials_permissions = $2 WHERE id = $1",
+        application.id, // $1
+        &permissions,   // $2
+    )
+    .execute(db_pool)
+    .await?;
+
+    remove_application_cache(application).await;
+
+    Ok(())
+}
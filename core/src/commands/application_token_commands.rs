@@ -9,11 +9,14 @@ use toolbox::rand::random_string;
 use toolbox::validator::{OrValidationErrors, ValidationResult};
 
 use crate::config::APPLICATION_TOKEN_CONFIG;
-use crate::constants::{CACHE_PREFIX_GET_APPLICATION_TOKEN_BY_CODE, CACHE_PREFIX_GET_APPLICATION_TOKEN_BY_ID};
+use crate::constants::{CACHE_PREFIX_GET_APPLICATION_TOKEN_BY_CODE_DIGEST, CACHE_PREFIX_GET_APPLICATION_TOKEN_BY_ID};
 use crate::db_pool;
+use crate::enums::Permission;
 use crate::models::{Application, ApplicationToken};
 use crate::params::ApplicationTokenParams;
 
+use super::digest_code;
+
 pub async fn all_application_tokens<'a>(application: &Application<'_>) -> sqlx::Result<Vec<ApplicationToken<'a>>> {
     let db_pool = db_pool().await;
 
@@ -27,16 +30,20 @@ pub async fn all_application_tokens<'a>(application: &Application<'_>) -> sqlx::
     .await
 }
 
-#[io_cached(
-    map_error = r##"|_| sqlx::Error::RowNotFound"##,
-    ty = "AsyncRedisCache<String, ApplicationToken<'_>>",
-    create = r##"{ redis_cache_store(CACHE_PREFIX_GET_APPLICATION_TOKEN_BY_CODE).await }"##
-)]
-pub async fn get_application_token_by_code(code: String) -> sqlx::Result<ApplicationToken<'static>> {
+pub async fn get_application_token_by_code(code: &str) -> sqlx::Result<ApplicationToken<'static>> {
     if code.is_empty() {
         return Err(sqlx::Error::RowNotFound);
     }
 
+    get_application_token_by_code_digest(digest_code(code)).await
+}
+
+#[io_cached(
+    map_error = r##"|_| sqlx::Error::RowNotFound"##,
+    ty = "AsyncRedisCache<String, ApplicationToken<'_>>",
+    create = r##"{ redis_cache_store(CACHE_PREFIX_GET_APPLICATION_TOKEN_BY_CODE_DIGEST).await }"##
+)]
+async fn get_application_token_by_code_digest(code_digest: String) -> sqlx::Result<ApplicationToken<'static>> {
     let db_pool = db_pool().await;
 
     sqlx::query_as!(
@@ -44,7 +51,7 @@ pub async fn get_application_token_by_code(code: String) -> sqlx::Result<Applica
         "SELECT * FROM application_tokens
         WHERE code = $1 AND expires_at > current_timestamp AND revoked_at IS NULL
         LIMIT 1",
-        code // $1
+        code_digest // $1
     )
     .fetch_one(db_pool)
     .await
@@ -83,19 +90,82 @@ pub async fn insert_application_token<'a>(
         .map(|date| date.and_time(Utc::now().time()).and_utc())
         .unwrap_or_else(|| Utc::now() + APPLICATION_TOKEN_CONFIG.ttl());
 
-    let access_token = sqlx::query_as!(
+    let mut application_token = sqlx::query_as!(
         ApplicationToken,
-        "INSERT INTO application_tokens (application_id, name, code, expires_at) VALUES ($1, $2, $3, $4) RETURNING *",
-        application.id, // $1
-        params.name,    // $2
-        code,           // $3
-        expires_at,     // $4
+        "INSERT INTO application_tokens (application_id, name, code, expires_at, scopes, permissions)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING *",
+        application.id,      // $1
+        params.name,         // $2
+        digest_code(&code),  // $3
+        expires_at,          // $4
+        &params.scopes,      // $5
+        &params.permissions, // $6
     )
     .fetch_one(db_pool)
     .await
     .or_validation_errors()?;
 
-    Ok(access_token)
+    application_token.code = code.into();
+
+    Ok(application_token)
+}
+
+pub async fn insert_client_credentials_token<'a>(
+    application: &Application<'_>,
+    scopes: &[String],
+) -> sqlx::Result<ApplicationToken<'a>> {
+    let db_pool = db_pool().await;
+
+    let name = format!("client_credentials_{}", random_string(16..=16));
+    let code = random_string(APPLICATION_TOKEN_CONFIG.length());
+    let expires_at = Utc::now() + APPLICATION_TOKEN_CONFIG.client_credentials_ttl();
+
+    let mut application_token = sqlx::query_as!(
+        ApplicationToken,
+        "INSERT INTO application_tokens (application_id, name, code, expires_at, scopes, permissions)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING *",
+        application.id,                              // $1
+        name,                                        // $2
+        digest_code(&code),                          // $3
+        expires_at,                                  // $4
+        scopes,                                      // $5
+        &application.client_credentials_permissions, // $6
+    )
+    .fetch_one(db_pool)
+    .await?;
+
+    application_token.code = code.into();
+
+    Ok(application_token)
+}
+
+pub async fn insert_registration_access_token<'a>(application: &Application<'_>) -> sqlx::Result<ApplicationToken<'a>> {
+    let db_pool = db_pool().await;
+
+    let name = format!("registration_{}", random_string(16..=16));
+    let code = random_string(APPLICATION_TOKEN_CONFIG.length());
+    let expires_at = Utc::now() + APPLICATION_TOKEN_CONFIG.ttl();
+    let permissions = [Permission::ClientsManage.to_string()];
+
+    let mut application_token = sqlx::query_as!(
+        ApplicationToken,
+        "INSERT INTO application_tokens (application_id, name, code, expires_at, permissions)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING *",
+        application.id,     // $1
+        name,               // $2
+        digest_code(&code), // $3
+        expires_at,         // $4
+        &permissions,       // $5
+    )
+    .fetch_one(db_pool)
+    .await?;
+
+    application_token.code = code.into();
+
+    Ok(application_token)
 }
 
 pub async fn revoke_application_token(application_token: &ApplicationToken<'_>) -> sqlx::Result<()> {
@@ -105,14 +175,15 @@ pub async fn revoke_application_token(application_token: &ApplicationToken<'_>)
 
     let db_pool = db_pool().await;
 
-    sqlx::query!(
-        "UPDATE application_tokens SET revoked_at = current_timestamp WHERE id = $1",
+    let revoked_application_token = sqlx::query_as!(
+        ApplicationToken,
+        "UPDATE application_tokens SET revoked_at = current_timestamp WHERE id = $1 RETURNING *",
         application_token.id // $1
     )
-    .execute(db_pool)
+    .fetch_one(db_pool)
     .await?;
 
-    remove_application_token_cache(application_token).await;
+    remove_application_token_cache(&revoked_application_token).await;
 
     Ok(())
 }
@@ -120,7 +191,33 @@ pub async fn revoke_application_token(application_token: &ApplicationToken<'_>)
 async fn remove_application_token_cache(application_token: &ApplicationToken<'_>) {
     let code = application_token.code.to_string();
 
-    GET_APPLICATION_TOKEN_BY_CODE
-        .cache_remove(CACHE_PREFIX_GET_APPLICATION_TOKEN_BY_CODE, &code)
-        .await;
+    tokio::join!(
+        GET_APPLICATION_TOKEN_BY_CODE_DIGEST.cache_remove(CACHE_PREFIX_GET_APPLICATION_TOKEN_BY_CODE_DIGEST, &code),
+        GET_APPLICATION_TOKEN_BY_ID.cache_remove(CACHE_PREFIX_GET_APPLICATION_TOKEN_BY_ID, &application_token.id)
+    );
+}
+
+pub async fn update_application_token_permissions(
+    application_token: &ApplicationToken<'_>,
+    permissions: &[Permission],
+) -> sqlx::Result<()> {
+    let db_pool = db_pool().await;
+
+    let permissions = permissions
+        .iter()
+        .map(|permission| permission.to_string())
+        .collect::<Vec<_>>();
+
+    let application_token = sqlx::query_as!(
+        ApplicationToken,
+        "UPDATE application_tokens SET permissions = $2 WHERE id = $1 RETURNING *",
+        application_token.id, // $1
+        &permissions,         // $2
+    )
+    .fetch_one(db_pool)
+    .await?;
+
+    remove_application_token_cache(&application_token).await;
+
+    Ok(())
 }
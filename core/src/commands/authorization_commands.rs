@@ -7,24 +7,48 @@ use sha2::{Digest, Sha256};
 use url::Url;
 use uuid::Uuid;
 
-use toolbox::cache::redis_cache_store;
+use toolbox::cache::{AsyncRedisCacheExt, redis_cache_store};
 use toolbox::rand::random_string;
 
 use crate::config::AUTHORIZATION_CONFIG;
-use crate::constants::{CACHE_PREFIX_GET_AUTHORIZATION_BY_CODE, CACHE_PREFIX_GET_AUTHORIZATION_BY_ID};
+use crate::constants::{CACHE_PREFIX_GET_AUTHORIZATION_BY_CODE_DIGEST, CACHE_PREFIX_GET_AUTHORIZATION_BY_ID};
 use crate::db_pool;
-use crate::models::{Application, Authorization, Session};
+use crate::enums::SecurityEventKind;
+use crate::models::{Application, Authorization, Session, User};
 
-#[io_cached(
-    map_error = r##"|_| sqlx::Error::RowNotFound"##,
-    ty = "AsyncRedisCache<String, Authorization<'_>>",
-    create = r##"{ redis_cache_store(CACHE_PREFIX_GET_AUTHORIZATION_BY_CODE).await }"##
-)]
-pub async fn get_authorization_by_code(code: String) -> sqlx::Result<Authorization<'static>> {
+use super::{
+    digest_code, insert_security_event, revoke_access_tokens_by_authorization_code,
+    revoke_access_tokens_by_authorization_id,
+};
+
+pub async fn all_authorizations_by_user<'a>(user: &User<'_>) -> sqlx::Result<Vec<Authorization<'a>>> {
+    let db_pool = db_pool().await;
+
+    sqlx::query_as!(
+        Authorization,
+        "SELECT * FROM authorizations
+        WHERE user_id = $1 AND revoked_at IS NULL
+        ORDER BY coalesce(updated_at, created_at) DESC",
+        user.id // $1
+    )
+    .fetch_all(db_pool)
+    .await
+}
+
+pub async fn get_authorization_by_code(code: &str) -> sqlx::Result<Authorization<'static>> {
     if code.is_empty() {
         return Err(sqlx::Error::RowNotFound);
     }
 
+    get_authorization_by_code_digest(digest_code(code)).await
+}
+
+#[io_cached(
+    map_error = r##"|_| sqlx::Error::RowNotFound"##,
+    ty = "AsyncRedisCache<String, Authorization<'_>>",
+    create = r##"{ redis_cache_store(CACHE_PREFIX_GET_AUTHORIZATION_BY_CODE_DIGEST).await }"##
+)]
+async fn get_authorization_by_code_digest(code_digest: String) -> sqlx::Result<Authorization<'static>> {
     let db_pool = db_pool().await;
 
     sqlx::query_as!(
@@ -32,7 +56,7 @@ pub async fn get_authorization_by_code(code: String) -> sqlx::Result<Authorizati
         "SELECT * FROM authorizations
         WHERE expires_at > current_timestamp AND revoked_at IS NULL AND code = $1
         LIMIT 1",
-        code // $1
+        code_digest // $1
     )
     .fetch_one(db_pool)
     .await
@@ -58,10 +82,13 @@ pub async fn get_authorization_by_id(id: Uuid) -> sqlx::Result<Authorization<'st
 pub async fn insert_or_refresh_authorization<'a>(
     application: &Application<'_>,
     session: &Session,
-    redirect_url: Url,
+    redirect_url: &Url,
     code_challenge: &str,
+    scopes: &[String],
+    nonce: Option<&str>,
+    state: Option<&str>,
 ) -> sqlx::Result<Authorization<'a>> {
-    if redirect_url != application.redirect_url() {
+    if !application.has_redirect_url(redirect_url) {
         return Err(sqlx::Error::InvalidArgument("Invalid redirect URL".to_owned()));
     }
 
@@ -71,23 +98,98 @@ pub async fn insert_or_refresh_authorization<'a>(
     let code = random_string(AUTHORIZATION_CONFIG.length());
     let expires_at = Utc::now() + AUTHORIZATION_CONFIG.ttl();
 
-    sqlx::query_as!(
+    let mut authorization = sqlx::query_as!(
         Authorization,
-        "INSERT INTO authorizations AS a (application_id, session_id, user_id, redirect_url, code, code_challenge, expires_at)
-        VALUES ($1, $2, $3, $4, $5, $6, $7)
-        ON CONFLICT (application_id, user_id) DO UPDATE
-        SET session_id = $2, redirect_url = $4, code = $5, code_challenge = $6, expires_at = $7, revoked_at = NULL
+        "INSERT INTO authorizations AS a (
+            application_id, session_id, user_id, redirect_url, code, code_challenge, expires_at, scopes, nonce, state
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        ON CONFLICT (application_id, user_id) WHERE redirect_url IS NOT NULL DO UPDATE
+        SET
+            session_id = $2, redirect_url = $4, code = $5, code_challenge = $6, expires_at = $7, scopes = $8,
+            nonce = $9, state = $10, code_redeemed_at = NULL, revoked_at = NULL
         RETURNING *",
         application.id,        // $1
         session.id,            // $2
         user.id,               // $3
-        redirect_url.as_ref(), // $4
-        code,                  // $5
+        redirect_url.as_str(), // $4
+        digest_code(&code),    // $5
         code_challenge,        // $6
         expires_at,            // $7
+        scopes,                // $8
+        nonce,                 // $9
+        state,                 // $10
     )
     .fetch_one(db_pool)
-    .await
+    .await?;
+
+    authorization.code = code.into();
+
+    Ok(authorization)
+}
+
+pub async fn redeem_authorization_code(authorization: &Authorization<'_>) -> sqlx::Result<bool> {
+    let db_pool = db_pool().await;
+
+    let redeemed_authorization = sqlx::query_as!(
+        Authorization,
+        "UPDATE authorizations SET code_redeemed_at = current_timestamp
+        WHERE id = $1 AND code = $2 AND code_redeemed_at IS NULL
+        RETURNING *",
+        authorization.id,            // $1
+        authorization.code.as_ref(), // $2
+    )
+    .fetch_optional(db_pool)
+    .await?;
+
+    let Some(redeemed_authorization) = redeemed_authorization else {
+        return Ok(false);
+    };
+
+    remove_authorization_cache(&redeemed_authorization).await;
+
+    Ok(true)
+}
+
+async fn remove_authorization_cache(authorization: &Authorization<'_>) {
+    let code = authorization.code.to_string();
+
+    tokio::join!(
+        GET_AUTHORIZATION_BY_CODE_DIGEST.cache_remove(CACHE_PREFIX_GET_AUTHORIZATION_BY_CODE_DIGEST, &code),
+        GET_AUTHORIZATION_BY_ID.cache_remove(CACHE_PREFIX_GET_AUTHORIZATION_BY_ID, &authorization.id)
+    );
+}
+
+pub async fn revoke_authorization(authorization: &Authorization<'_>) -> sqlx::Result<()> {
+    let db_pool = db_pool().await;
+
+    let revoked_authorization = sqlx::query_as!(
+        Authorization,
+        "UPDATE authorizations SET revoked_at = current_timestamp WHERE id = $1 AND revoked_at IS NULL RETURNING *",
+        authorization.id // $1
+    )
+    .fetch_optional(db_pool)
+    .await?;
+
+    if let Some(revoked_authorization) = revoked_authorization {
+        remove_authorization_cache(&revoked_authorization).await;
+    }
+
+    revoke_access_tokens_by_authorization_id(authorization.id).await
+}
+
+pub async fn revoke_replayed_authorization_code(authorization: &Authorization<'_>) -> sqlx::Result<()> {
+    revoke_access_tokens_by_authorization_code(&authorization.code).await?;
+
+    insert_security_event(
+        SecurityEventKind::AuthorizationCodeReused,
+        authorization.user_id,
+        Some(authorization.application_id),
+        Some(authorization.id),
+    )
+    .await?;
+
+    Ok(())
 }
 
 pub fn verify_authorization_code_challenge(authorization: &Authorization<'_>, code_verifier: &str) -> bool {
@@ -98,5 +200,5 @@ pub fn verify_authorization_code_challenge(authorization: &Authorization<'_>, co
     let code_verifier_hash = hasher.finalize();
     let code_verifier_base64 = BASE64_URL_SAFE_NO_PAD.encode(code_verifier_hash);
 
-    authorization.code_challenge == code_verifier_base64
+    authorization.code_challenge.as_deref() == Some(code_verifier_base64.as_str())
 }
@@ -0,0 +1,223 @@
+use chrono::Utc;
+use rand::rngs::OsRng;
+use rand::seq::SliceRandom;
+
+use toolbox::rand::random_string;
+
+use crate::config::{AUTHORIZATION_CONFIG, DEVICE_CODE_CONFIG};
+use crate::db_pool;
+use crate::models::{Application, Authorization, DeviceAuthorization, Session};
+
+use super::digest_code;
+
+const SLOW_DOWN_INTERVAL_SECS: i32 = 5;
+const USER_CODE_CHARS: &[u8] = b"BCDFGHJKLMNPQRSTVWXZ";
+
+fn generate_user_code() -> String {
+    let chars = (0..8)
+        .filter_map(|_| USER_CODE_CHARS.choose(&mut OsRng).map(|char| *char as char))
+        .collect::<String>();
+
+    format!("{}-{}", &chars[0..4], &chars[4..8])
+}
+
+fn normalize_user_code(user_code: &str) -> String {
+    let chars = user_code
+        .chars()
+        .filter(|char| char.is_ascii_alphanumeric())
+        .map(|char| char.to_ascii_uppercase())
+        .collect::<String>();
+
+    if chars.len() != 8 {
+        return chars;
+    }
+
+    format!("{}-{}", &chars[0..4], &chars[4..8])
+}
+
+pub async fn approve_device_authorization(
+    device_authorization: &DeviceAuthorization<'_>,
+    session: &Session,
+) -> sqlx::Result<()> {
+    let db_pool = db_pool().await;
+
+    let code = random_string(AUTHORIZATION_CONFIG.length());
+    let expires_at = Utc::now() + AUTHORIZATION_CONFIG.ttl();
+
+    let mut transaction = db_pool.begin().await?;
+
+    let authorization = sqlx::query_as!(
+        Authorization,
+        "INSERT INTO authorizations (application_id, session_id, user_id, code, expires_at, scopes)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING *",
+        device_authorization.application_id, // $1
+        session.id,                          // $2
+        session.user_id,                     // $3
+        digest_code(&code),                  // $4
+        expires_at,                          // $5
+        &device_authorization.scopes,        // $6
+    )
+    .fetch_one(&mut *transaction)
+    .await?;
+
+    let result = sqlx::query!(
+        "UPDATE device_authorizations SET authorization_id = $2
+        WHERE id = $1 AND authorization_id IS NULL AND denied_at IS NULL",
+        device_authorization.id, // $1
+        authorization.id,        // $2
+    )
+    .execute(&mut *transaction)
+    .await?;
+
+    if result.rows_affected() != 1 {
+        transaction.rollback().await?;
+
+        return Err(sqlx::Error::RowNotFound);
+    }
+
+    transaction.commit().await
+}
+
+pub async fn delete_device_authorization(device_authorization: &DeviceAuthorization<'_>) -> sqlx::Result<()> {
+    let db_pool = db_pool().await;
+
+    let result = sqlx::query!(
+        "DELETE FROM device_authorizations WHERE id = $1",
+        device_authorization.id // $1
+    )
+    .execute(db_pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(sqlx::Error::RowNotFound);
+    }
+
+    Ok(())
+}
+
+pub async fn deny_device_authorization(device_authorization: &DeviceAuthorization<'_>) -> sqlx::Result<()> {
+    let db_pool = db_pool().await;
+
+    sqlx::query!(
+        "UPDATE device_authorizations SET denied_at = current_timestamp
+        WHERE id = $1 AND authorization_id IS NULL AND denied_at IS NULL",
+        device_authorization.id // $1
+    )
+    .execute(db_pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn get_device_authorization_by_device_code<'a>(device_code: &str) -> sqlx::Result<DeviceAuthorization<'a>> {
+    if device_code.is_empty() {
+        return Err(sqlx::Error::RowNotFound);
+    }
+
+    let db_pool = db_pool().await;
+
+    sqlx::query_as!(
+        DeviceAuthorization,
+        "SELECT * FROM device_authorizations WHERE device_code = $1 LIMIT 1",
+        digest_code(device_code) // $1
+    )
+    .fetch_one(db_pool)
+    .await
+}
+
+pub async fn get_pending_device_authorization_by_user_code<'a>(
+    user_code: &str,
+) -> sqlx::Result<DeviceAuthorization<'a>> {
+    let db_pool = db_pool().await;
+
+    sqlx::query_as!(
+        DeviceAuthorization,
+        "SELECT * FROM device_authorizations
+        WHERE
+            expires_at > current_timestamp AND authorization_id IS NULL AND denied_at IS NULL AND user_code = $1
+        LIMIT 1",
+        normalize_user_code(user_code) // $1
+    )
+    .fetch_one(db_pool)
+    .await
+}
+
+pub async fn insert_device_authorization<'a>(
+    application: &Application<'_>,
+    scopes: &[String],
+) -> sqlx::Result<DeviceAuthorization<'a>> {
+    let db_pool = db_pool().await;
+
+    let device_code = random_string(AUTHORIZATION_CONFIG.length());
+    let expires_at = Utc::now() + DEVICE_CODE_CONFIG.ttl();
+    let interval_secs = DEVICE_CODE_CONFIG.interval().as_secs() as i32;
+
+    let mut device_authorization = sqlx::query_as!(
+        DeviceAuthorization,
+        "INSERT INTO device_authorizations (application_id, device_code, user_code, scopes, expires_at, interval_secs)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING *",
+        application.id,            // $1
+        digest_code(&device_code), // $2
+        generate_user_code(),      // $3
+        scopes,                    // $4
+        expires_at,                // $5
+        interval_secs,             // $6
+    )
+    .fetch_one(db_pool)
+    .await?;
+
+    device_authorization.device_code = device_code.into();
+
+    Ok(device_authorization)
+}
+
+pub async fn update_device_authorization_polled_at(
+    device_authorization: &DeviceAuthorization<'_>,
+    slow_down: bool,
+) -> sqlx::Result<()> {
+    let db_pool = db_pool().await;
+
+    sqlx::query!(
+        "UPDATE device_authorizations
+        SET
+            polled_at = current_timestamp,
+            interval_secs = CASE WHEN $2 IS TRUE THEN interval_secs + $3 ELSE interval_secs END
+        WHERE id = $1",
+        device_authorization.id, // $1
+        slow_down,               // $2
+        SLOW_DOWN_INTERVAL_SECS, // $3
+    )
+    .execute(db_pool)
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_user_code_uses_only_user_code_chars() {
+        let user_code = generate_user_code();
+
+        assert_eq!(user_code.len(), 9);
+        assert_eq!(&user_code[4..5], "-");
+        assert!(
+            user_code
+                .bytes()
+                .filter(|byte| *byte != b'-')
+                .all(|byte| USER_CODE_CHARS.contains(&byte))
+        );
+    }
+
+    #[test]
+    fn normalize_user_code_formats_entered_codes() {
+        assert_eq!(normalize_user_code("bcdf-ghjk"), "BCDF-GHJK");
+        assert_eq!(normalize_user_code(" BCDF GHJK "), "BCDF-GHJK");
+        assert_eq!(normalize_user_code("bcdfghjk"), "BCDF-GHJK");
+        assert_eq!(normalize_user_code("bcd"), "BCD");
+    }
+}
@@ -0,0 +1,52 @@
+use std::collections::HashMap;
+
+use chrono::{TimeDelta, Utc};
+
+use toolbox::rand::random_string;
+
+use crate::claims::{BACKCHANNEL_LOGOUT_EVENT, IdTokenClaims, LogoutTokenClaims};
+use crate::config::{API_CONFIG, ID_TOKEN_CONFIG};
+use crate::models::{Application, Authorization, Session};
+
+use super::{decode_jwt, encode_jwt};
+
+pub async fn encode_id_token(authorization: &Authorization<'_>, session: &Session) -> anyhow::Result<String> {
+    let user = authorization.user().await?;
+    let issued_at = Utc::now();
+    let expires_at = issued_at + ID_TOKEN_CONFIG.ttl();
+
+    let claims = IdTokenClaims {
+        iss: API_CONFIG.issuer(),
+        sub: user.id,
+        aud: authorization.application_id,
+        exp: expires_at.timestamp(),
+        iat: issued_at.timestamp(),
+        auth_time: session.authenticated_at.timestamp(),
+        nonce: authorization.nonce.clone(),
+        sid: Some(session.id),
+    };
+
+    Ok(encode_jwt(&claims, "JWT")?)
+}
+
+pub fn decode_id_token_hint(id_token_hint: &str) -> Option<IdTokenClaims> {
+    decode_jwt(id_token_hint, false).ok()
+}
+
+pub fn encode_logout_token(application: &Application<'_>, session: &Session) -> anyhow::Result<String> {
+    let issued_at = Utc::now();
+    let expires_at = issued_at + TimeDelta::minutes(2);
+
+    let claims = LogoutTokenClaims {
+        iss: API_CONFIG.issuer(),
+        sub: session.user_id,
+        aud: application.id,
+        iat: issued_at.timestamp(),
+        exp: expires_at.timestamp(),
+        jti: random_string(32..=32),
+        sid: session.id,
+        events: HashMap::from([(BACKCHANNEL_LOGOUT_EVENT.to_owned(), HashMap::new())]),
+    };
+
+    Ok(encode_jwt(&claims, "logout+jwt")?)
+}
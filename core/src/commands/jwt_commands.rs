@@ -0,0 +1,111 @@
+use std::sync::LazyLock;
+
+use base64::Engine;
+use base64::prelude::BASE64_URL_SAFE_NO_PAD;
+use jsonwebtoken::errors::ErrorKind;
+use jsonwebtoken::jwk::{
+    AlgorithmParameters, CommonParameters, EllipticCurve, Jwk, JwkSet, KeyAlgorithm, OctetKeyPairParameters,
+    OctetKeyPairType, PublicKeyUse, RSAKeyParameters, RSAKeyType,
+};
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use rsa::RsaPublicKey;
+use rsa::pkcs8::DecodePublicKey;
+use rsa::traits::PublicKeyParts;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::config::{API_CONFIG, JWT_CONFIG};
+
+static JWT_ENCODING_KEY: LazyLock<EncodingKey> = LazyLock::new(|| JWT_CONFIG.encoding_key());
+static JWT_DECODING_KEYS: LazyLock<Vec<(String, Algorithm, DecodingKey)>> = LazyLock::new(|| {
+    JWT_CONFIG
+        .public_keys()
+        .into_iter()
+        .map(|(key_id, algorithm, public_key)| {
+            let decoding_key = match algorithm {
+                Algorithm::EdDSA => DecodingKey::from_ed_pem(&public_key),
+                _ => DecodingKey::from_rsa_pem(&public_key),
+            }
+            .expect("Could not load JWT public key");
+
+            (key_id, algorithm, decoding_key)
+        })
+        .collect()
+});
+static JWK_SET: LazyLock<JwkSet> = LazyLock::new(|| JwkSet {
+    keys: JWT_CONFIG
+        .public_keys()
+        .into_iter()
+        .map(|(key_id, algorithm, public_key)| jwk_from_public_key(key_id, algorithm, &public_key))
+        .collect(),
+});
+
+pub(crate) fn encode_jwt<T: Serialize>(claims: &T, typ: &str) -> jsonwebtoken::errors::Result<String> {
+    let mut header = Header::new(JWT_CONFIG.algorithm());
+
+    header.typ = Some(typ.to_owned());
+    header.kid = Some(JWT_CONFIG.key_id.clone());
+
+    jsonwebtoken::encode(&header, claims, &JWT_ENCODING_KEY)
+}
+
+pub(crate) fn decode_jwt<T: DeserializeOwned>(token: &str, validate_exp: bool) -> jsonwebtoken::errors::Result<T> {
+    let header = jsonwebtoken::decode_header(token)?;
+    let Some((_, algorithm, decoding_key)) = JWT_DECODING_KEYS
+        .iter()
+        .find(|(key_id, ..)| header.kid.as_ref() == Some(key_id))
+    else {
+        return Err(ErrorKind::InvalidKeyFormat.into());
+    };
+    let mut validation = Validation::new(*algorithm);
+
+    validation.validate_aud = false;
+    validation.validate_exp = validate_exp;
+    validation.set_issuer(&[API_CONFIG.issuer()]);
+
+    Ok(jsonwebtoken::decode::<T>(token, decoding_key, &validation)?.claims)
+}
+
+fn jwk_from_public_key(key_id: String, algorithm: Algorithm, public_key: &[u8]) -> Jwk {
+    let (key_algorithm, algorithm_parameters) = match algorithm {
+        Algorithm::EdDSA => {
+            let decoding_key = DecodingKey::from_ed_pem(public_key).expect("Could not load JWT public key");
+
+            (
+                KeyAlgorithm::EdDSA,
+                AlgorithmParameters::OctetKeyPair(OctetKeyPairParameters {
+                    key_type: OctetKeyPairType::OctetKeyPair,
+                    curve: EllipticCurve::Ed25519,
+                    x: BASE64_URL_SAFE_NO_PAD.encode(decoding_key.as_bytes()),
+                }),
+            )
+        }
+        _ => {
+            let public_key = std::str::from_utf8(public_key).expect("Could not read JWT public key");
+            let rsa_public_key = RsaPublicKey::from_public_key_pem(public_key).expect("Could not load JWT public key");
+
+            (
+                KeyAlgorithm::RS256,
+                AlgorithmParameters::RSA(RSAKeyParameters {
+                    key_type: RSAKeyType::RSA,
+                    n: BASE64_URL_SAFE_NO_PAD.encode(rsa_public_key.n().to_bytes_be()),
+                    e: BASE64_URL_SAFE_NO_PAD.encode(rsa_public_key.e().to_bytes_be()),
+                }),
+            )
+        }
+    };
+
+    Jwk {
+        common: CommonParameters {
+            public_key_use: Some(PublicKeyUse::Signature),
+            key_algorithm: Some(key_algorithm),
+            key_id: Some(key_id),
+            ..Default::default()
+        },
+        algorithm: algorithm_parameters,
+    }
+}
+
+pub fn jwk_set<'a>() -> &'a JwkSet {
+    &JWK_SET
+}
@@ -5,6 +5,7 @@ use argon2::{Argon2, PasswordHash, PasswordHasher, PasswordVerifier};
 use image::{ImageBuffer, Rgb, RgbImage};
 use imageproc::drawing::{draw_filled_rect_mut, draw_text_mut, text_size};
 use imageproc::rect::Rect;
+use sha2::{Digest, Sha256};
 
 use crate::config::STORAGE_CONFIG;
 
@@ -13,6 +14,11 @@ mod application_commands;
 mod application_token_commands;
 mod authorization_commands;
 mod confirmation_commands;
+mod device_authorization_commands;
+mod id_token_commands;
+mod jwt_commands;
+mod pushed_authorization_request_commands;
+mod security_event_commands;
 mod session_commands;
 mod user_commands;
 
@@ -21,9 +27,21 @@ pub use application_commands::*;
 pub use application_token_commands::*;
 pub use authorization_commands::*;
 pub use confirmation_commands::*;
+pub use device_authorization_commands::*;
+pub use id_token_commands::*;
+pub use jwt_commands::*;
+pub use pushed_authorization_request_commands::*;
+pub use security_event_commands::*;
 pub use session_commands::*;
 pub use user_commands::*;
 
+pub(crate) fn digest_code(code: &str) -> String {
+    Sha256::digest(code.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
 fn encrypt_password(value: &str) -> String {
     let salt = SaltString::generate(&mut OsRng);
     let argon2 = Argon2::default();
@@ -31,7 +49,7 @@ fn encrypt_password(value: &str) -> String {
 }
 
 pub(crate) fn generate_text_icon(text: &str, size: u32) -> anyhow::Result<ImageBuffer<Rgb<u8>, Vec<u8>>> {
-    let text = &text[0..2].to_uppercase();
+    let text = &text.chars().take(2).collect::<String>().to_uppercase();
     let mut rgb_image = RgbImage::new(size, size);
 
     draw_filled_rect_mut(
@@ -61,3 +79,16 @@ pub(crate) fn verify_password(encrypted_password: &str, password: &str) -> bool
 
     argon2.verify_password(password.as_bytes(), &password_hash).is_ok()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digest_code_returns_the_sha256_hex_digest() {
+        assert_eq!(
+            digest_code("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
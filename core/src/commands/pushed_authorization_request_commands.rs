@@ -0,0 +1,92 @@
+use chrono::Utc;
+use url::Url;
+use uuid::Uuid;
+
+use toolbox::rand::random_string;
+
+use crate::config::{AUTHORIZATION_CONFIG, PAR_CONFIG};
+use crate::db_pool;
+use crate::models::{Application, PushedAuthorizationRequest};
+
+const REQUEST_URI_PREFIX: &str = "urn:ietf:params:oauth:request_uri:";
+
+pub async fn delete_pushed_authorization_request(
+    pushed_authorization_request: &PushedAuthorizationRequest<'_>,
+) -> sqlx::Result<()> {
+    let db_pool = db_pool().await;
+
+    let result = sqlx::query!(
+        "DELETE FROM pushed_authorization_requests WHERE id = $1",
+        pushed_authorization_request.id // $1
+    )
+    .execute(db_pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(sqlx::Error::RowNotFound);
+    }
+
+    Ok(())
+}
+
+pub async fn get_pushed_authorization_request<'a>(
+    application_id: Uuid,
+    request_uri: &str,
+) -> sqlx::Result<PushedAuthorizationRequest<'a>> {
+    let db_pool = db_pool().await;
+
+    sqlx::query_as!(
+        PushedAuthorizationRequest,
+        "SELECT * FROM pushed_authorization_requests
+        WHERE expires_at > current_timestamp AND application_id = $1 AND request_uri = $2
+        LIMIT 1",
+        application_id, // $1
+        request_uri,    // $2
+    )
+    .fetch_one(db_pool)
+    .await
+}
+
+pub async fn insert_pushed_authorization_request<'a>(
+    application: &Application<'_>,
+    redirect_url: &Url,
+    code_challenge: &str,
+    scope: Option<&str>,
+    nonce: Option<&str>,
+    state: Option<&str>,
+    prompt: Option<&str>,
+    max_age: Option<i32>,
+    login_hint: Option<&str>,
+) -> sqlx::Result<PushedAuthorizationRequest<'a>> {
+    if !application.has_redirect_url(redirect_url) {
+        return Err(sqlx::Error::InvalidArgument("Invalid redirect URL".to_owned()));
+    }
+
+    let db_pool = db_pool().await;
+
+    let request_uri = format!("{REQUEST_URI_PREFIX}{}", random_string(AUTHORIZATION_CONFIG.length()));
+    let expires_at = Utc::now() + PAR_CONFIG.ttl();
+
+    sqlx::query_as!(
+        PushedAuthorizationRequest,
+        "INSERT INTO pushed_authorization_requests (
+            application_id, request_uri, redirect_url, code_challenge, scope, nonce, state, expires_at, prompt,
+            max_age, login_hint
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+        RETURNING *",
+        application.id,        // $1
+        request_uri,           // $2
+        redirect_url.as_str(), // $3
+        code_challenge,        // $4
+        scope,                 // $5
+        nonce,                 // $6
+        state,                 // $7
+        expires_at,            // $8
+        prompt,                // $9
+        max_age,               // $10
+        login_hint,            // $11
+    )
+    .fetch_one(db_pool)
+    .await
+}
@@ -0,0 +1,33 @@
+use uuid::Uuid;
+
+use crate::db_pool;
+use crate::enums::SecurityEventKind;
+use crate::models::SecurityEvent;
+
+pub async fn insert_security_event(
+    kind: SecurityEventKind,
+    user_id: Uuid,
+    application_id: Option<Uuid>,
+    authorization_id: Option<Uuid>,
+) -> sqlx::Result<SecurityEvent> {
+    let db_pool = db_pool().await;
+
+    sqlx::query_as!(
+        SecurityEvent,
+        r#"INSERT INTO security_events (user_id, application_id, authorization_id, kind) VALUES ($1, $2, $3, $4)
+            RETURNING
+                id,
+                user_id,
+                application_id,
+                authorization_id,
+                kind as "kind!: SecurityEventKind",
+                created_at,
+                updated_at"#,
+        user_id,                   // $1
+        application_id,            // $2
+        authorization_id,          // $3
+        kind as SecurityEventKind, // $4
+    )
+    .fetch_one(db_pool)
+    .await
+}
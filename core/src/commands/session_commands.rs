@@ -15,7 +15,7 @@ use super::revoke_access_token;
 pub async fn finish_session(session: &Session) -> sqlx::Result<()> {
     let db_pool = db_pool().await;
 
-    sqlx::query!(
+    let result = sqlx::query!(
         "UPDATE sessions SET finished_at = current_timestamp WHERE finished_at IS NULL AND id = $1",
         session.id
     )
@@ -24,6 +24,10 @@ pub async fn finish_session(session: &Session) -> sqlx::Result<()> {
 
     remove_session_cache(session).await;
 
+    if result.rows_affected() > 0 {
+        jobs_storage().await.push_session_finished(session).await;
+    }
+
     if let Ok(access_tokens) = session.access_tokens().await {
         for access_token in access_tokens {
             let _ = revoke_access_token(&access_token).await;
@@ -84,6 +88,21 @@ pub async fn insert_session(user: &User<'_>, ip_address: IpAddr) -> sqlx::Result
     }
 }
 
+pub async fn reauthenticate_session(session: &Session) -> sqlx::Result<()> {
+    let db_pool = db_pool().await;
+
+    sqlx::query!(
+        "UPDATE sessions SET authenticated_at = current_timestamp WHERE finished_at IS NULL AND id = $1",
+        session.id, // $1
+    )
+    .execute(db_pool)
+    .await?;
+
+    remove_session_cache(session).await;
+
+    Ok(())
+}
+
 pub async fn refresh_session(session: &Session) -> sqlx::Result<()> {
     let db_pool = db_pool().await;
 
@@ -55,27 +55,6 @@ pub async fn confirm_user_email(user: &User<'_>, params: ConfirmationParams) ->
     .await
 }
 
-#[io_cached(
-    map_error = r##"|_| sqlx::Error::RowNotFound"##,
-    ty = "AsyncRedisCache<String, User<'_>>",
-    create = r##"{ redis_cache_store(CACHE_PREFIX_GET_USER_BY_ACCESS_TOKEN_CODE).await }"##
-)]
-pub async fn get_user_by_access_token_code(code: String) -> sqlx::Result<User<'static>> {
-    let db_pool = db_pool().await;
-
-    sqlx::query_as!(
-        User,
-        "SELECT us.* FROM users AS us, access_tokens AS at
-        WHERE
-            us.id = at.user_id AND us.disabled_at IS NULL AND at.code_expires_at > current_timestamp
-            AND at.revoked_at IS NULL AND at.code = $1
-        LIMIT 1",
-        code, // $1
-    )
-    .fetch_one(db_pool)
-    .await
-}
-
 #[io_cached(
     map_error = r##"|_| sqlx::Error::RowNotFound"##,
     ty = "AsyncRedisCache<Uuid, User<'_>>",
@@ -370,6 +349,27 @@ pub async fn update_user_profile(user: &User<'_>, params: ProfileParams) -> Resu
     Ok(())
 }
 
+pub async fn update_user_developer(user: &User<'_>, developer: bool) -> sqlx::Result<()> {
+    let db_pool = db_pool().await;
+
+    sqlx::query!(
+        "UPDATE users
+        SET developer_at = CASE
+            WHEN $2 IS TRUE AND developer_at IS NOT NULL THEN developer_at
+            WHEN $2 IS TRUE THEN current_timestamp
+            ELSE NULL END
+        WHERE disabled_at IS NULL AND id = $1",
+        user.id,   // $1
+        developer, // $2
+    )
+    .execute(db_pool)
+    .await?;
+
+    remove_user_cache(user).await;
+
+    Ok(())
+}
+
 pub(crate) async fn user_email_exists(email: &str) -> bool {
     get_user_id_by_email(email).await.is_ok()
 }
@@ -5,11 +5,16 @@ use std::sync::LazyLock;
 use std::time::Duration;
 
 use envconfig::Envconfig;
+use jsonwebtoken::{Algorithm, EncodingKey};
 use url::Url;
 
 pub static API_CONFIG: LazyLock<ApiConfig> = LazyLock::new(|| ApiConfig::init_from_env().unwrap());
+pub static APP_CONFIG: LazyLock<AppConfig> = LazyLock::new(|| AppConfig::init_from_env().unwrap());
+pub static JWT_CONFIG: LazyLock<JwtConfig> = LazyLock::new(|| JwtConfig::init_from_env().unwrap());
 pub(crate) static ACCESS_TOKEN_CONFIG: LazyLock<AccessTokenConfig> =
     LazyLock::new(|| AccessTokenConfig::init_from_env().unwrap());
+pub(crate) static APPLICATION_CONFIG: LazyLock<ApplicationConfig> =
+    LazyLock::new(|| ApplicationConfig::init_from_env().unwrap());
 pub(crate) static APPLICATION_TOKEN_CONFIG: LazyLock<ApplicationTokenConfig> =
     LazyLock::new(|| ApplicationTokenConfig::init_from_env().unwrap());
 pub(crate) static AUTHORIZATION_CONFIG: LazyLock<AuthorizationConfig> =
@@ -18,7 +23,11 @@ pub(crate) static CONFIRMATION_CONFIG: LazyLock<ConfirmationConfig> =
     LazyLock::new(|| ConfirmationConfig::init_from_env().unwrap());
 pub(crate) static DATABASE_CONFIG: LazyLock<DatabaseConfig> =
     LazyLock::new(|| DatabaseConfig::init_from_env().unwrap());
+pub(crate) static DEVICE_CODE_CONFIG: LazyLock<DeviceCodeConfig> =
+    LazyLock::new(|| DeviceCodeConfig::init_from_env().unwrap());
+pub(crate) static ID_TOKEN_CONFIG: LazyLock<IdTokenConfig> = LazyLock::new(|| IdTokenConfig::init_from_env().unwrap());
 pub(crate) static MONITOR_CONFIG: LazyLock<MonitorConfig> = LazyLock::new(|| MonitorConfig::init_from_env().unwrap());
+pub(crate) static PAR_CONFIG: LazyLock<ParConfig> = LazyLock::new(|| ParConfig::init_from_env().unwrap());
 pub(crate) static STORAGE_CONFIG: LazyLock<StorageConfig> = LazyLock::new(|| StorageConfig::init_from_env().unwrap());
 
 #[derive(Envconfig)]
@@ -55,8 +64,38 @@ pub struct ApiConfig {
     pub(crate) url: Url,
 }
 
+impl ApiConfig {
+    pub fn endpoint_url(&self, path: &str) -> Url {
+        self.url.join(path).unwrap()
+    }
+
+    pub fn issuer(&self) -> String {
+        self.url.as_str().trim_end_matches('/').to_owned()
+    }
+}
+
+#[derive(Envconfig)]
+pub struct AppConfig {
+    #[envconfig(from = "APP_URL", default = "http://127.0.0.1:8000")]
+    pub(crate) url: Url,
+}
+
+impl AppConfig {
+    pub fn page_url(&self, path: &str) -> Url {
+        self.url.join(path).unwrap()
+    }
+}
+
+#[derive(Envconfig)]
+pub(crate) struct ApplicationConfig {
+    #[envconfig(from = "APPLICATION_SECRET_LENGTH", default = "64")]
+    pub secret_length: u8,
+}
+
 #[derive(Envconfig)]
 pub(crate) struct ApplicationTokenConfig {
+    #[envconfig(from = "CLIENT_CREDENTIALS_TTL_SECS", default = "3600")]
+    client_credentials_ttl_secs: u64,
     #[envconfig(from = "APPLICATION_TOKEN_MIN_LENGTH", default = "64")]
     min_length: u8,
     #[envconfig(from = "APPLICATION_TOKEN_MAX_LENGTH", default = "128")]
@@ -66,6 +105,10 @@ pub(crate) struct ApplicationTokenConfig {
 }
 
 impl ApplicationTokenConfig {
+    pub fn client_credentials_ttl(&self) -> Duration {
+        Duration::from_secs(self.client_credentials_ttl_secs)
+    }
+
     pub fn length(&self) -> RangeInclusive<u8> {
         self.min_length..=self.max_length
     }
@@ -112,12 +155,117 @@ pub(crate) struct DatabaseConfig {
     pub url: String,
 }
 
+#[derive(Envconfig)]
+pub(crate) struct DeviceCodeConfig {
+    #[envconfig(from = "DEVICE_CODE_INTERVAL_SECS", default = "5")]
+    interval_secs: u64,
+    #[envconfig(from = "DEVICE_CODE_TTL_SECS", default = "600")]
+    ttl_secs: u64,
+}
+
+impl DeviceCodeConfig {
+    pub(crate) fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_secs)
+    }
+
+    pub(crate) fn ttl(&self) -> Duration {
+        Duration::from_secs(self.ttl_secs)
+    }
+}
+
+#[derive(Envconfig)]
+pub(crate) struct IdTokenConfig {
+    #[envconfig(from = "ID_TOKEN_TTL_SECS", default = "3600")]
+    ttl_secs: u64,
+}
+
+impl IdTokenConfig {
+    pub fn ttl(&self) -> Duration {
+        Duration::from_secs(self.ttl_secs)
+    }
+}
+
+#[derive(Envconfig)]
+pub struct JwtConfig {
+    #[envconfig(from = "JWT_ALGORITHM", default = "RS256")]
+    algorithm: String,
+    #[envconfig(from = "JWT_KEY_ID", default = "default")]
+    pub(crate) key_id: String,
+    #[envconfig(from = "JWT_PRIVATE_KEY_PATH", default = "./keys/jwt_private_key.pem")]
+    private_key_path: PathBuf,
+    #[envconfig(from = "JWT_PUBLIC_KEY_PATH", default = "./keys/jwt_public_key.pem")]
+    public_key_path: PathBuf,
+    #[envconfig(from = "JWT_PREVIOUS_ALGORITHM")]
+    previous_algorithm: Option<String>,
+    #[envconfig(from = "JWT_PREVIOUS_KEY_ID")]
+    previous_key_id: Option<String>,
+    #[envconfig(from = "JWT_PREVIOUS_PUBLIC_KEY_PATH")]
+    previous_public_key_path: Option<PathBuf>,
+}
+
+impl JwtConfig {
+    fn parse_algorithm(value: &str) -> Algorithm {
+        match value {
+            "EdDSA" => Algorithm::EdDSA,
+            "RS256" => Algorithm::RS256,
+            _ => panic!("Unsupported JWT algorithm"),
+        }
+    }
+
+    pub fn algorithm(&self) -> Algorithm {
+        Self::parse_algorithm(&self.algorithm)
+    }
+
+    pub(crate) fn encoding_key(&self) -> EncodingKey {
+        let private_key = std::fs::read(&self.private_key_path).expect("Could not read JWT private key");
+
+        match self.algorithm() {
+            Algorithm::EdDSA => EncodingKey::from_ed_pem(&private_key),
+            _ => EncodingKey::from_rsa_pem(&private_key),
+        }
+        .expect("Could not load JWT private key")
+    }
+
+    pub(crate) fn public_keys(&self) -> Vec<(String, Algorithm, Vec<u8>)> {
+        let public_key = std::fs::read(&self.public_key_path).expect("Could not read JWT public key");
+        let mut public_keys = vec![(self.key_id.clone(), self.algorithm(), public_key)];
+
+        if let (Some(previous_key_id), Some(previous_public_key_path)) =
+            (&self.previous_key_id, &self.previous_public_key_path)
+        {
+            let previous_algorithm = self
+                .previous_algorithm
+                .as_deref()
+                .map(Self::parse_algorithm)
+                .unwrap_or_else(|| self.algorithm());
+            let previous_public_key =
+                std::fs::read(previous_public_key_path).expect("Could not read previous JWT public key");
+
+            public_keys.push((previous_key_id.clone(), previous_algorithm, previous_public_key));
+        }
+
+        public_keys
+    }
+}
+
 #[derive(Envconfig)]
 pub(crate) struct MonitorConfig {
     #[envconfig(from = "MONITOR_REDIS_URL", default = "redis://127.0.0.1:6379/1")]
     pub redis_url: String,
 }
 
+#[derive(Envconfig)]
+pub(crate) struct ParConfig {
+    #[envconfig(from = "PAR_TTL_SECS", default = "60")]
+    ttl_secs: u64,
+}
+
+impl ParConfig {
+    pub fn ttl(&self) -> Duration {
+        Duration::from_secs(self.ttl_secs)
+    }
+}
+
 #[derive(Envconfig)]
 pub struct SentryConfig {
     #[envconfig(from = "SENTRY_DSN")]
@@ -10,15 +10,16 @@ pub static ERROR_PASSWORD_MUST_CHANGE: LazyLock<ValidationError> = LazyLock::new
 
 pub static REGEX_USERNAME: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\A[-_.]?([[:alnum:]]+[-_.]?)+\z").unwrap());
 
-pub const CACHE_PREFIX_GET_ACCESS_TOKEN_BY_CODE: &str = "get_access_token_by_code";
-pub const CACHE_PREFIX_GET_ACCESS_TOKEN_BY_REFRESH_CODE: &str = "get_access_token_by_refresh_code";
+pub const APPLICATION_LOGO_MAX_BYTES: usize = 1024 * 1024;
+
+pub const CACHE_PREFIX_GET_ACCESS_TOKEN_BY_CODE_DIGEST: &str = "get_access_token_by_code_digest";
+pub const CACHE_PREFIX_GET_ACCESS_TOKEN_BY_REFRESH_CODE_DIGEST: &str = "get_access_token_by_refresh_code_digest";
 pub const CACHE_PREFIX_GET_APPLICATION_BY_ID: &str = "get_application_by_id";
-pub const CACHE_PREFIX_GET_APPLICATION_TOKEN_BY_CODE: &str = "get_application_token_by_code";
+pub const CACHE_PREFIX_GET_APPLICATION_TOKEN_BY_CODE_DIGEST: &str = "get_application_token_by_code_digest";
 pub const CACHE_PREFIX_GET_APPLICATION_TOKEN_BY_ID: &str = "get_application_token_by_id";
-pub const CACHE_PREFIX_GET_AUTHORIZATION_BY_CODE: &str = "get_authorization_by_code";
+pub const CACHE_PREFIX_GET_AUTHORIZATION_BY_CODE_DIGEST: &str = "get_authorization_by_code_digest";
 pub const CACHE_PREFIX_GET_AUTHORIZATION_BY_ID: &str = "get_authorization_by_id";
 pub const CACHE_PREFIX_GET_SESSION_BY_ID: &str = "get_session_by_id";
-pub const CACHE_PREFIX_GET_USER_BY_ACCESS_TOKEN_CODE: &str = "get_user_by_access_token_code";
 pub const CACHE_PREFIX_GET_USER_BY_ID: &str = "get_user_by_id";
 pub const CACHE_PREFIX_GET_USER_BY_USERNAME: &str = "get_user_by_username";
 pub const CACHE_PREFIX_GET_USER_BY_USERNAME_OR_EMAIL: &str = "get_user_by_username_or_email";
@@ -1,3 +1,6 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
 #[derive(sqlx::Type, Clone, Copy, PartialEq)]
 #[sqlx(type_name = "confirmation_action", rename_all = "snake_case")]
 pub enum ConfirmationAction {
@@ -5,3 +8,128 @@ pub enum ConfirmationAction {
     Login,
     PasswordReset,
 }
+
+#[derive(sqlx::Type, Clone, Copy, PartialEq)]
+#[sqlx(type_name = "security_event_kind", rename_all = "snake_case")]
+pub enum SecurityEventKind {
+    AuthorizationCodeReused,
+    RefreshTokenReused,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Permission {
+    ClientsManage,
+    ClientsRegister,
+    TokensIntrospect,
+    UsersRead,
+    UsersReadEmail,
+}
+
+impl Permission {
+    pub const ALL: [Permission; 5] = [
+        Permission::ClientsManage,
+        Permission::ClientsRegister,
+        Permission::TokensIntrospect,
+        Permission::UsersRead,
+        Permission::UsersReadEmail,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Permission::ClientsManage => "clients:manage",
+            Permission::ClientsRegister => "clients:register",
+            Permission::TokensIntrospect => "tokens:introspect",
+            Permission::UsersRead => "users:read",
+            Permission::UsersReadEmail => "users:read_email",
+        }
+    }
+
+    pub fn scope(&self) -> Option<Scope> {
+        match self {
+            Permission::UsersRead => Some(Scope::Profile),
+            Permission::UsersReadEmail => Some(Scope::Email),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Permission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for Permission {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|permission| permission.as_str() == value)
+            .ok_or(())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Scope {
+    Email,
+    OfflineAccess,
+    Openid,
+    Profile,
+}
+
+impl Scope {
+    pub const ALL: [Scope; 4] = [Scope::Email, Scope::OfflineAccess, Scope::Openid, Scope::Profile];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Scope::Email => "email",
+            Scope::OfflineAccess => "offline_access",
+            Scope::Openid => "openid",
+            Scope::Profile => "profile",
+        }
+    }
+
+    pub fn all_strings() -> Vec<String> {
+        Self::ALL.iter().map(|scope| scope.to_string()).collect()
+    }
+}
+
+impl Display for Scope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for Scope {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::ALL.into_iter().find(|scope| scope.as_str() == value).ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn permission_scope_maps_user_permissions_to_scopes() {
+        assert!(Permission::UsersRead.scope() == Some(Scope::Profile));
+        assert!(Permission::UsersReadEmail.scope() == Some(Scope::Email));
+        assert!(Permission::ClientsManage.scope().is_none());
+        assert!(Permission::ClientsRegister.scope().is_none());
+        assert!(Permission::TokensIntrospect.scope().is_none());
+    }
+
+    #[test]
+    fn permission_and_scope_round_trip_through_strings() {
+        for permission in Permission::ALL {
+            assert!(permission.as_str().parse::<Permission>() == Ok(permission));
+        }
+
+        for scope in Scope::ALL {
+            assert!(scope.as_str().parse::<Scope>() == Ok(scope));
+        }
+    }
+}
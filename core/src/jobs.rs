@@ -21,3 +21,8 @@ pub struct NewUserJob {
 pub struct PasswordChangedJob {
     pub user_id: Uuid,
 }
+
+#[derive(Deserialize, Serialize)]
+pub struct SessionFinishedJob {
+    pub session_id: Uuid,
+}
@@ -8,6 +8,7 @@ use tokio::sync::OnceCell;
 
 mod constants;
 
+pub mod claims;
 pub mod commands;
 pub mod config;
 pub mod enums;
@@ -16,7 +17,7 @@ pub mod models;
 pub mod params;
 
 use crate::config::{DATABASE_CONFIG, MONITOR_CONFIG};
-use crate::jobs::{NewConfirmationJob, NewSessionJob, NewUserJob, PasswordChangedJob};
+use crate::jobs::{NewConfirmationJob, NewSessionJob, NewUserJob, PasswordChangedJob, SessionFinishedJob};
 use crate::models::{Confirmation, Session, User};
 
 static DB_POOL_CELL: OnceCell<PgPool> = OnceCell::const_new();
@@ -49,6 +50,7 @@ pub struct JobsStorage {
     pub new_session: RedisStorage<NewSessionJob>,
     pub new_user: RedisStorage<NewUserJob>,
     pub password_changed: RedisStorage<PasswordChangedJob>,
+    pub session_finished: RedisStorage<SessionFinishedJob>,
 }
 
 impl JobsStorage {
@@ -58,6 +60,7 @@ impl JobsStorage {
             new_session: Self::storage().await,
             new_user: Self::storage().await,
             password_changed: Self::storage().await,
+            session_finished: Self::storage().await,
         }
     }
 
@@ -103,6 +106,14 @@ impl JobsStorage {
             .await
             .expect("Could not store job");
     }
+
+    pub(crate) async fn push_session_finished(&self, session: &Session) {
+        self.session_finished
+            .clone()
+            .push(SessionFinishedJob { session_id: session.id })
+            .await
+            .expect("Could not store job");
+    }
 }
 
 #[derive(Serialize)]
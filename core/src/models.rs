@@ -1,15 +1,18 @@
 use std::borrow::Cow;
 use std::fmt::Display;
 use std::path::PathBuf;
+use std::time::Duration;
 
 use chrono::{DateTime, NaiveDate, TimeDelta, Utc};
+use image::DynamicImage;
+use image::imageops::FilterType;
 use serde::{Deserialize, Serialize};
 use url::Url;
 use uuid::Uuid;
 
 use crate::commands;
-use crate::config::{API_CONFIG, STORAGE_CONFIG};
-use crate::enums::ConfirmationAction;
+use crate::config::{ACCESS_TOKEN_CONFIG, API_CONFIG, STORAGE_CONFIG};
+use crate::enums::{ConfirmationAction, Permission, Scope, SecurityEventKind};
 
 #[derive(Clone, Deserialize, Serialize)]
 pub struct AccessToken<'a> {
@@ -26,6 +29,9 @@ pub struct AccessToken<'a> {
     pub revoked_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
+    pub scopes: Vec<String>,
+    pub refresh_started_at: DateTime<Utc>,
+    pub authorization_code: Option<String>,
 }
 
 impl Display for AccessToken<'_> {
@@ -50,16 +56,41 @@ impl AccessToken<'_> {
     pub fn code_expires_in(&self) -> TimeDelta {
         self.code_expires_at - Utc::now()
     }
+
+    pub fn has_scope(&self, scope: Scope) -> bool {
+        self.scopes.iter().any(|value| value == scope.as_str())
+    }
+
+    pub async fn user<'a>(&self) -> sqlx::Result<User<'a>> {
+        commands::get_user_by_id(self.user_id).await
+    }
 }
 
 #[derive(Clone, Deserialize, Serialize)]
 pub struct Application<'a> {
     pub id: Uuid,
     pub name: Cow<'a, str>,
-    pub redirect_url: Cow<'a, str>,
     pub trusted_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
+    pub allowed_scopes: Vec<String>,
+    pub encrypted_secret: Option<String>,
+    pub confidential_at: Option<DateTime<Utc>>,
+    pub redirect_urls: Vec<String>,
+    pub jwt_access_tokens_at: Option<DateTime<Utc>>,
+    pub par_required_at: Option<DateTime<Utc>>,
+    pub backchannel_logout_url: Option<String>,
+    pub description: Option<String>,
+    pub homepage_url: Option<String>,
+    pub privacy_policy_url: Option<String>,
+    pub terms_url: Option<String>,
+    pub logo_updated_at: Option<DateTime<Utc>>,
+    pub owner_id: Option<Uuid>,
+    pub access_token_ttl_secs: Option<i32>,
+    pub refresh_token_ttl_secs: Option<i32>,
+    pub refresh_token_idle_ttl_secs: Option<i32>,
+    pub client_credentials_permissions: Vec<String>,
+    pub post_logout_redirect_urls: Vec<String>,
 }
 
 impl Display for Application<'_> {
@@ -69,12 +100,178 @@ impl Display for Application<'_> {
 }
 
 impl Application<'_> {
+    pub fn access_token_ttl(&self) -> Duration {
+        self.access_token_ttl_secs
+            .map(|secs| Duration::from_secs(secs as u64))
+            .unwrap_or_else(|| ACCESS_TOKEN_CONFIG.code_ttl())
+    }
+
+    pub fn backchannel_logout_url(&self) -> Option<Url> {
+        self.backchannel_logout_url
+            .as_ref()
+            .and_then(|value| value.parse().ok())
+    }
+
+    pub fn granted_scopes(&self, scope: Option<&str>) -> Option<Vec<String>> {
+        let Some(scope) = scope else {
+            return Some(
+                self.allowed_scopes
+                    .iter()
+                    .filter(|value| *value != Scope::Openid.as_str())
+                    .cloned()
+                    .collect(),
+            );
+        };
+
+        let scopes = scope
+            .split_whitespace()
+            .map(|value| value.to_owned())
+            .collect::<Vec<_>>();
+
+        if scopes.iter().all(|value| self.allowed_scopes.contains(value)) {
+            Some(scopes)
+        } else {
+            None
+        }
+    }
+
+    pub fn error_redirect_url(&self, redirect_url: &Url, error: &str, state: Option<&str>) -> Option<Url> {
+        if !self.has_redirect_url(redirect_url) {
+            return None;
+        }
+
+        let mut url = redirect_url.clone();
+
+        url.query_pairs_mut().append_pair("error", error);
+
+        if let Some(state) = state {
+            url.query_pairs_mut().append_pair("state", state);
+        }
+
+        url.query_pairs_mut().append_pair("iss", &API_CONFIG.issuer());
+
+        Some(url)
+    }
+
+    pub fn has_logo(&self) -> bool {
+        self.logo_updated_at.is_some()
+    }
+
+    pub fn has_redirect_url(&self, redirect_url: &Url) -> bool {
+        self.redirect_urls
+            .iter()
+            .any(|value| Url::parse(value).is_ok_and(|value| value == *redirect_url))
+    }
+
+    pub fn homepage_url(&self) -> Option<Url> {
+        self.homepage_url.as_ref().and_then(|value| value.parse().ok())
+    }
+
+    pub fn is_confidential(&self) -> bool {
+        self.confidential_at.is_some()
+    }
+
+    pub fn is_owned_by(&self, user: &User<'_>) -> bool {
+        self.owner_id == Some(user.id)
+    }
+
+    pub fn logo_image(&self, size: u32) -> anyhow::Result<Vec<u8>> {
+        let logo_image_path = self.logo_image_path(size);
+
+        if !logo_image_path.exists() {
+            let logo_image = if self.has_logo() {
+                image::open(self.original_logo_image_path())?
+                    .resize_to_fill(size, size, FilterType::Lanczos3)
+                    .to_rgba8()
+            } else {
+                DynamicImage::from(commands::generate_text_icon(&self.name, size)?).to_rgba8()
+            };
+
+            std::fs::create_dir_all(
+                logo_image_path
+                    .parent()
+                    .ok_or_else(|| anyhow::anyhow!("Failed to create directory"))?,
+            )?;
+
+            logo_image.save(&logo_image_path)?;
+        }
+
+        Ok(std::fs::read(&logo_image_path)?)
+    }
+
+    pub fn logo_image_path(&self, size: u32) -> PathBuf {
+        STORAGE_CONFIG
+            .path
+            .join(format!("application_logo_images/{size}x{size}/{}.png", self.id))
+    }
+
+    pub fn logo_image_url(&self) -> Url {
+        API_CONFIG
+            .url
+            .join(&format!("applications/{}/logo-image", self.id))
+            .unwrap()
+    }
+
+    pub fn original_logo_image_path(&self) -> PathBuf {
+        STORAGE_CONFIG
+            .path
+            .join(format!("application_logo_images/original/{}.png", self.id))
+    }
+
+    pub fn post_logout_redirect_url(&self, redirect_url: &Url, state: Option<&str>) -> Option<Url> {
+        if !self
+            .post_logout_redirect_urls
+            .iter()
+            .any(|value| Url::parse(value).is_ok_and(|value| value == *redirect_url))
+        {
+            return None;
+        }
+
+        let mut url = redirect_url.clone();
+
+        if let Some(state) = state {
+            url.query_pairs_mut().append_pair("state", state);
+        }
+
+        Some(url)
+    }
+
+    pub fn privacy_policy_url(&self) -> Option<Url> {
+        self.privacy_policy_url.as_ref().and_then(|value| value.parse().ok())
+    }
+
+    pub fn refresh_token_idle_ttl(&self) -> Duration {
+        self.refresh_token_idle_ttl_secs
+            .map(|secs| Duration::from_secs(secs as u64))
+            .unwrap_or_else(|| ACCESS_TOKEN_CONFIG.ttl())
+    }
+
+    pub fn refresh_token_ttl(&self) -> Option<Duration> {
+        self.refresh_token_ttl_secs.map(|secs| Duration::from_secs(secs as u64))
+    }
+
+    pub fn requires_par(&self) -> bool {
+        self.par_required_at.is_some()
+    }
+
+    pub fn terms_url(&self) -> Option<Url> {
+        self.terms_url.as_ref().and_then(|value| value.parse().ok())
+    }
+
+    pub fn uses_jwt_access_tokens(&self) -> bool {
+        self.jwt_access_tokens_at.is_some()
+    }
+
     pub fn is_trusted(&self) -> bool {
         self.trusted_at.is_some()
     }
 
-    pub fn redirect_url(&self) -> Url {
-        Url::parse(&self.redirect_url).expect("Could not get Redirect URL")
+    pub fn verify_secret(&self, secret: &str) -> bool {
+        let Some(encrypted_secret) = &self.encrypted_secret else {
+            return false;
+        };
+
+        commands::verify_password(encrypted_secret, secret)
     }
 }
 
@@ -88,6 +285,8 @@ pub struct ApplicationToken<'a> {
     pub revoked_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
+    pub scopes: Vec<String>,
+    pub permissions: Vec<String>,
 }
 
 impl Display for ApplicationToken<'_> {
@@ -96,6 +295,16 @@ impl Display for ApplicationToken<'_> {
     }
 }
 
+impl ApplicationToken<'_> {
+    pub fn expires_in(&self) -> TimeDelta {
+        self.expires_at - Utc::now()
+    }
+
+    pub fn has_permission(&self, permission: Permission) -> bool {
+        self.permissions.iter().any(|value| value == permission.as_str())
+    }
+}
+
 #[derive(Clone, Deserialize, Serialize)]
 pub struct Authorization<'a> {
     pub id: Uuid,
@@ -103,12 +312,16 @@ pub struct Authorization<'a> {
     pub session_id: Uuid,
     pub user_id: Uuid,
     pub code: Cow<'a, str>,
-    pub code_challenge: Cow<'a, str>,
-    pub redirect_url: Cow<'a, str>,
+    pub code_challenge: Option<String>,
+    pub redirect_url: Option<String>,
     pub expires_at: DateTime<Utc>,
     pub revoked_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
+    pub scopes: Vec<String>,
+    pub nonce: Option<String>,
+    pub code_redeemed_at: Option<DateTime<Utc>>,
+    pub state: Option<String>,
 }
 
 impl Display for Authorization<'_> {
@@ -118,20 +331,36 @@ impl Display for Authorization<'_> {
 }
 
 impl Authorization<'_> {
+    pub async fn access_tokens(&self) -> sqlx::Result<Vec<AccessToken<'_>>> {
+        commands::all_access_tokens_by_authorization(self).await
+    }
+
     pub async fn application<'a>(&self) -> sqlx::Result<Application<'a>> {
         commands::get_application_by_id(self.application_id).await
     }
 
-    pub fn full_redirect_url(&self) -> Url {
-        let mut url = self.redirect_url();
+    pub fn full_redirect_url(&self) -> Option<Url> {
+        let mut url = self.redirect_url()?;
+
+        url.query_pairs_mut().append_pair("code", &self.code);
+
+        if let Some(state) = &self.state {
+            url.query_pairs_mut().append_pair("state", state);
+        }
 
-        url.set_query(Some(&format!("code={}", self.code)));
+        url.query_pairs_mut().append_pair("iss", &API_CONFIG.issuer());
 
-        url
+        Some(url)
     }
 
-    pub fn redirect_url(&self) -> Url {
-        Url::parse(&self.redirect_url).expect("Could not get Redirect URL")
+    pub fn has_scope(&self, scope: Scope) -> bool {
+        self.scopes.iter().any(|value| value == scope.as_str())
+    }
+
+    pub fn redirect_url(&self) -> Option<Url> {
+        self.redirect_url
+            .as_ref()
+            .map(|redirect_url| Url::parse(redirect_url).expect("Could not get Redirect URL"))
     }
 
     pub async fn session(&self) -> sqlx::Result<Session> {
@@ -170,6 +399,88 @@ impl Confirmation<'_> {
     }
 }
 
+#[derive(Clone)]
+pub struct DeviceAuthorization<'a> {
+    pub id: Uuid,
+    pub application_id: Uuid,
+    pub authorization_id: Option<Uuid>,
+    pub device_code: Cow<'a, str>,
+    pub user_code: Cow<'a, str>,
+    pub scopes: Vec<String>,
+    pub polled_at: Option<DateTime<Utc>>,
+    pub denied_at: Option<DateTime<Utc>>,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub interval_secs: i32,
+}
+
+impl DeviceAuthorization<'_> {
+    pub async fn application<'a>(&self) -> sqlx::Result<Application<'a>> {
+        commands::get_application_by_id(self.application_id).await
+    }
+
+    pub fn expires_in(&self) -> TimeDelta {
+        self.expires_at - Utc::now()
+    }
+
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_secs as u64)
+    }
+
+    pub fn is_denied(&self) -> bool {
+        self.denied_at.is_some()
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= Utc::now()
+    }
+
+    pub fn is_polled_too_soon(&self) -> bool {
+        self.polled_at
+            .is_some_and(|polled_at| polled_at + self.interval() > Utc::now())
+    }
+}
+
+#[derive(Clone)]
+pub struct PushedAuthorizationRequest<'a> {
+    pub id: Uuid,
+    pub application_id: Uuid,
+    pub request_uri: Cow<'a, str>,
+    pub redirect_url: String,
+    pub code_challenge: String,
+    pub scope: Option<String>,
+    pub nonce: Option<String>,
+    pub state: Option<String>,
+    pub prompt: Option<String>,
+    pub max_age: Option<i32>,
+    pub login_hint: Option<String>,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl PushedAuthorizationRequest<'_> {
+    pub fn expires_in(&self) -> TimeDelta {
+        self.expires_at - Utc::now()
+    }
+
+    pub fn redirect_url(&self) -> Url {
+        self.redirect_url.parse().unwrap()
+    }
+}
+
+#[derive(Clone)]
+pub struct SecurityEvent {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub application_id: Option<Uuid>,
+    pub authorization_id: Option<Uuid>,
+    pub kind: SecurityEventKind,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
 #[derive(Clone, Deserialize, Serialize)]
 pub struct Session {
     pub id: Uuid,
@@ -183,6 +494,7 @@ pub struct Session {
     pub finished_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
+    pub authenticated_at: DateTime<Utc>,
 }
 
 impl Display for Session {
@@ -238,6 +550,7 @@ pub struct User<'a> {
     pub disabled_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
+    pub developer_at: Option<DateTime<Utc>>,
 }
 
 impl Display for User<'_> {
@@ -283,7 +596,159 @@ impl User<'_> {
         self.username[0..2].to_uppercase()
     }
 
+    pub fn is_developer(&self) -> bool {
+        self.developer_at.is_some()
+    }
+
     pub(crate) fn verify_password(&self, password: &str) -> bool {
         commands::verify_password(&self.encrypted_password, password)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn application(allowed_scopes: &[&str]) -> Application<'static> {
+        Application {
+            id: Uuid::new_v4(),
+            name: "Test".into(),
+            trusted_at: None,
+            created_at: Utc::now(),
+            updated_at: None,
+            allowed_scopes: allowed_scopes.iter().map(|scope| scope.to_string()).collect(),
+            encrypted_secret: None,
+            confidential_at: None,
+            redirect_urls: vec!["https://example.com/callback".to_owned()],
+            jwt_access_tokens_at: None,
+            par_required_at: None,
+            backchannel_logout_url: None,
+            description: None,
+            homepage_url: None,
+            privacy_policy_url: None,
+            terms_url: None,
+            logo_updated_at: None,
+            owner_id: None,
+            access_token_ttl_secs: None,
+            refresh_token_ttl_secs: None,
+            refresh_token_idle_ttl_secs: None,
+            client_credentials_permissions: Vec::new(),
+            post_logout_redirect_urls: vec!["https://example.com/logged-out".to_owned()],
+        }
+    }
+
+    fn authorization(code_challenge: &str) -> Authorization<'static> {
+        Authorization {
+            id: Uuid::new_v4(),
+            application_id: Uuid::new_v4(),
+            session_id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            code: "code".into(),
+            code_challenge: Some(code_challenge.to_owned()),
+            redirect_url: Some("https://example.com/callback".to_owned()),
+            expires_at: Utc::now() + TimeDelta::minutes(10),
+            revoked_at: None,
+            created_at: Utc::now(),
+            updated_at: None,
+            scopes: Vec::new(),
+            nonce: None,
+            code_redeemed_at: None,
+            state: None,
+        }
+    }
+
+    fn device_authorization(
+        polled_at: Option<DateTime<Utc>>,
+        expires_at: DateTime<Utc>,
+    ) -> DeviceAuthorization<'static> {
+        DeviceAuthorization {
+            id: Uuid::new_v4(),
+            application_id: Uuid::new_v4(),
+            authorization_id: None,
+            device_code: "device_code".into(),
+            user_code: "BCDF-GHJK".into(),
+            scopes: Vec::new(),
+            polled_at,
+            denied_at: None,
+            expires_at,
+            created_at: Utc::now(),
+            updated_at: None,
+            interval_secs: 5,
+        }
+    }
+
+    #[test]
+    fn authorization_verify_code_challenge_uses_s256() {
+        // Example from RFC 7636, Appendix B.
+        let authorization = authorization("E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM");
+
+        assert!(authorization.verify_code_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"));
+        assert!(!authorization.verify_code_challenge("E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"));
+        assert!(!authorization.verify_code_challenge(""));
+    }
+
+    #[test]
+    fn device_authorization_is_polled_too_soon_within_the_interval() {
+        let expires_at = Utc::now() + TimeDelta::minutes(10);
+
+        assert!(!device_authorization(None, expires_at).is_polled_too_soon());
+        assert!(device_authorization(Some(Utc::now() - TimeDelta::seconds(1)), expires_at).is_polled_too_soon());
+        assert!(!device_authorization(Some(Utc::now() - TimeDelta::seconds(6)), expires_at).is_polled_too_soon());
+    }
+
+    #[test]
+    fn device_authorization_is_expired_after_expires_at() {
+        assert!(!device_authorization(None, Utc::now() + TimeDelta::minutes(10)).is_expired());
+        assert!(device_authorization(None, Utc::now() - TimeDelta::seconds(1)).is_expired());
+    }
+
+    #[test]
+    fn granted_scopes_without_scope_excludes_openid() {
+        let application = application(&["email", "offline_access", "openid", "profile"]);
+
+        assert_eq!(
+            application.granted_scopes(None),
+            Some(vec![
+                "email".to_owned(),
+                "offline_access".to_owned(),
+                "profile".to_owned()
+            ])
+        );
+    }
+
+    #[test]
+    fn granted_scopes_returns_requested_allowed_scopes() {
+        let application = application(&["email", "openid", "profile"]);
+
+        assert_eq!(
+            application.granted_scopes(Some("openid profile")),
+            Some(vec!["openid".to_owned(), "profile".to_owned()])
+        );
+        assert_eq!(application.granted_scopes(Some("")), Some(Vec::new()));
+    }
+
+    #[test]
+    fn post_logout_redirect_url_only_accepts_registered_post_logout_urls() {
+        let application = application(&[]);
+
+        assert_eq!(
+            application
+                .post_logout_redirect_url(&"https://example.com/logged-out".parse().unwrap(), Some("xyz"))
+                .map(|url| url.to_string()),
+            Some("https://example.com/logged-out?state=xyz".to_owned())
+        );
+        assert!(
+            application
+                .post_logout_redirect_url(&"https://example.com/callback".parse().unwrap(), None)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn granted_scopes_rejects_scopes_that_are_not_allowed() {
+        let application = application(&["openid", "profile"]);
+
+        assert_eq!(application.granted_scopes(Some("openid email")), None);
+        assert_eq!(application.granted_scopes(Some("admin")), None);
+    }
+}
@@ -1,11 +1,13 @@
 use chrono::{NaiveDate, Utc};
+use url::{Host, Url};
 use uuid::Uuid;
 use validator::{Validate, ValidationError};
 
 use toolbox::constants::{ERROR_ALREADY_EXISTS, ERROR_IS_INVALID};
 
 use crate::commands;
-use crate::constants::REGEX_USERNAME;
+use crate::constants::{APPLICATION_LOGO_MAX_BYTES, REGEX_USERNAME};
+use crate::enums::{Permission, Scope};
 
 fn validate_birthdate(value: &NaiveDate) -> Result<(), ValidationError> {
     if *value > Utc::now().date_naive() {
@@ -41,6 +43,46 @@ fn validate_expires_at(value: &NaiveDate) -> Result<(), ValidationError> {
     Ok(())
 }
 
+fn validate_logo(value: &[u8]) -> Result<(), ValidationError> {
+    if value.len() > APPLICATION_LOGO_MAX_BYTES || image::load_from_memory(value).is_err() {
+        return Err(ERROR_IS_INVALID.clone());
+    }
+
+    Ok(())
+}
+
+fn validate_permissions(value: &[String]) -> Result<(), ValidationError> {
+    if value.iter().any(|permission| permission.parse::<Permission>().is_err()) {
+        return Err(ERROR_IS_INVALID.clone());
+    }
+
+    Ok(())
+}
+
+fn validate_redirect_urls(value: &[String]) -> Result<(), ValidationError> {
+    if !value.iter().all(|redirect_url| is_valid_redirect_url(redirect_url)) {
+        return Err(ERROR_IS_INVALID.clone());
+    }
+
+    Ok(())
+}
+
+fn validate_scopes(value: &[String]) -> Result<(), ValidationError> {
+    if value.iter().any(|scope| scope.parse::<Scope>().is_err()) {
+        return Err(ERROR_IS_INVALID.clone());
+    }
+
+    Ok(())
+}
+
+fn validate_url(value: &str) -> Result<(), ValidationError> {
+    if !Url::parse(value).is_ok_and(|url| matches!(url.scheme(), "http" | "https")) {
+        return Err(ERROR_IS_INVALID.clone());
+    }
+
+    Ok(())
+}
+
 fn validate_username(value: &str) -> Result<(), ValidationError> {
     if uuid::Uuid::try_parse(value).is_ok() {
         return Err(ERROR_IS_INVALID.clone());
@@ -53,13 +95,63 @@ fn validate_username(value: &str) -> Result<(), ValidationError> {
     Ok(())
 }
 
+pub fn is_valid_redirect_url(value: &str) -> bool {
+    let Ok(url) = Url::parse(value) else {
+        return false;
+    };
+
+    if url.fragment().is_some() {
+        return false;
+    }
+
+    match url.scheme() {
+        "https" => true,
+        "http" => match url.host() {
+            Some(Host::Domain(domain)) => domain == "localhost",
+            Some(Host::Ipv4(ip)) => ip.is_loopback(),
+            Some(Host::Ipv6(ip)) => ip.is_loopback(),
+            None => false,
+        },
+        _ => false,
+    }
+}
+
 #[derive(Validate)]
 pub struct ApplicationParams {
     #[validate(length(min = 1, max = 255, message = "Can't be blank"))]
     pub name: String,
-    #[validate(url(message = "Is invalid"))]
-    pub redirect_url: String,
+    #[validate(
+        length(min = 1, message = "Can't be blank"),
+        custom(function = "validate_redirect_urls")
+    )]
+    pub redirect_urls: Vec<String>,
     pub trusted: bool,
+    #[validate(custom(function = "validate_scopes"))]
+    pub allowed_scopes: Vec<String>,
+    pub confidential: bool,
+    pub jwt_access_tokens: bool,
+    pub par_required: bool,
+    #[validate(custom(function = "validate_url"))]
+    pub backchannel_logout_url: Option<String>,
+    #[validate(custom(function = "validate_redirect_urls"))]
+    pub post_logout_redirect_urls: Vec<String>,
+    #[validate(length(max = 1000, message = "Is too long"))]
+    pub description: Option<String>,
+    #[validate(custom(function = "validate_url"))]
+    pub homepage_url: Option<String>,
+    #[validate(custom(function = "validate_url"))]
+    pub privacy_policy_url: Option<String>,
+    #[validate(custom(function = "validate_url"))]
+    pub terms_url: Option<String>,
+    #[validate(custom(function = "validate_logo"))]
+    pub logo: Option<Vec<u8>>,
+    pub owner_id: Option<Uuid>,
+    #[validate(range(min = 60, message = "Must be at least 60 seconds"))]
+    pub access_token_ttl_secs: Option<i32>,
+    #[validate(range(min = 60, message = "Must be at least 60 seconds"))]
+    pub refresh_token_ttl_secs: Option<i32>,
+    #[validate(range(min = 60, message = "Must be at least 60 seconds"))]
+    pub refresh_token_idle_ttl_secs: Option<i32>,
 }
 
 #[derive(Validate)]
@@ -68,6 +160,10 @@ pub struct ApplicationTokenParams {
     pub name: String,
     #[validate(custom(function = "validate_expires_at"))]
     pub expires_at: Option<NaiveDate>,
+    #[validate(custom(function = "validate_scopes"))]
+    pub scopes: Vec<String>,
+    #[validate(custom(function = "validate_permissions"))]
+    pub permissions: Vec<String>,
 }
 
 #[derive(Validate)]
@@ -147,3 +243,59 @@ pub struct UserParams {
     #[validate(custom(function = "validate_country_code"))]
     pub country_code: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_valid_redirect_url_accepts_https_and_loopback_http() {
+        assert!(is_valid_redirect_url("https://example.com/callback"));
+        assert!(is_valid_redirect_url("http://localhost:8080/callback"));
+        assert!(is_valid_redirect_url("http://127.0.0.1/callback"));
+        assert!(is_valid_redirect_url("http://[::1]:3000/callback"));
+    }
+
+    #[test]
+    fn is_valid_redirect_url_rejects_other_urls() {
+        assert!(!is_valid_redirect_url("http://example.com/callback"));
+        assert!(!is_valid_redirect_url("https://example.com/callback#fragment"));
+        assert!(!is_valid_redirect_url("javascript:alert(1)"));
+        assert!(!is_valid_redirect_url("data:text/html,<script>alert(1)</script>"));
+        assert!(!is_valid_redirect_url("file:///etc/passwd"));
+        assert!(!is_valid_redirect_url("not a url"));
+    }
+
+    #[test]
+    fn validate_redirect_urls_rejects_any_invalid_url() {
+        assert!(validate_redirect_urls(&["https://example.com/callback".to_owned()]).is_ok());
+        assert!(
+            validate_redirect_urls(&[
+                "https://example.com/callback".to_owned(),
+                "javascript:alert(1)".to_owned()
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn validate_url_only_accepts_http_and_https() {
+        assert!(validate_url("https://example.com").is_ok());
+        assert!(validate_url("http://example.com").is_ok());
+        assert!(validate_url("javascript:alert(1)").is_err());
+        assert!(validate_url("data:text/plain,hello").is_err());
+        assert!(validate_url("file:///etc/passwd").is_err());
+    }
+
+    #[test]
+    fn validate_scopes_rejects_unknown_scopes() {
+        assert!(validate_scopes(&["openid".to_owned(), "profile".to_owned()]).is_ok());
+        assert!(validate_scopes(&["admin".to_owned()]).is_err());
+    }
+
+    #[test]
+    fn validate_permissions_rejects_unknown_permissions() {
+        assert!(validate_permissions(&["users:read".to_owned()]).is_ok());
+        assert!(validate_permissions(&["users:write".to_owned()]).is_err());
+    }
+}
@@ -0,0 +1 @@
+ALTER TABLE authorizations DROP COLUMN scopes, DROP COLUMN nonce;
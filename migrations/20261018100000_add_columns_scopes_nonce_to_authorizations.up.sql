@@ -0,0 +1,3 @@
+ALTER TABLE authorizations
+ADD COLUMN scopes varchar[] NOT NULL DEFAULT '{}',
+ADD COLUMN nonce varchar NULL;
@@ -0,0 +1,3 @@
+ALTER TABLE access_tokens DROP COLUMN scopes;
+
+ALTER TABLE applications DROP COLUMN allowed_scopes;
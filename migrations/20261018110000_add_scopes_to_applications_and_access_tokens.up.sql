@@ -0,0 +1,8 @@
+ALTER TABLE applications
+ADD COLUMN allowed_scopes varchar[] NOT NULL DEFAULT '{email,offline_access,openid,profile}';
+
+ALTER TABLE access_tokens ADD COLUMN scopes varchar[] NOT NULL DEFAULT '{}';
+
+UPDATE authorizations SET scopes = '{email,offline_access,profile}' WHERE scopes = '{}';
+
+UPDATE access_tokens SET scopes = '{email,offline_access,profile}';
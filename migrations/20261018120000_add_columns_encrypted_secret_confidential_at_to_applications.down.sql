@@ -0,0 +1 @@
+ALTER TABLE applications DROP COLUMN encrypted_secret, DROP COLUMN confidential_at;
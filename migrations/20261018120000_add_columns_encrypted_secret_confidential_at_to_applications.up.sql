@@ -0,0 +1 @@
+ALTER TABLE applications ADD COLUMN encrypted_secret varchar NULL, ADD COLUMN confidential_at timestamptz NULL;
@@ -0,0 +1,4 @@
+ALTER TABLE application_tokens ADD COLUMN scopes varchar[] NOT NULL DEFAULT '{}';
+
+UPDATE application_tokens SET scopes = applications.allowed_scopes
+FROM applications WHERE applications.id = application_tokens.application_id;
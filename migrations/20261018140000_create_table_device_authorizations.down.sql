@@ -0,0 +1,5 @@
+DROP TABLE device_authorizations;
+
+DELETE FROM authorizations WHERE redirect_url IS NULL OR code_challenge IS NULL;
+
+ALTER TABLE authorizations ALTER COLUMN redirect_url SET NOT NULL, ALTER COLUMN code_challenge SET NOT NULL;
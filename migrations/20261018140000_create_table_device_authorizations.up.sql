@@ -0,0 +1,26 @@
+ALTER TABLE authorizations ALTER COLUMN redirect_url DROP NOT NULL, ALTER COLUMN code_challenge DROP NOT NULL;
+
+CREATE TABLE device_authorizations (
+    id uuid NOT NULL DEFAULT gen_random_uuid(),
+    application_id uuid NOT NULL,
+    authorization_id uuid NULL,
+    device_code citext NOT NULL,
+    user_code citext NOT NULL,
+    scopes varchar[] NOT NULL DEFAULT '{}',
+    polled_at timestamptz NULL,
+    denied_at timestamptz NULL,
+    expires_at timestamptz NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT current_timestamp,
+    updated_at timestamptz NULL,
+    CONSTRAINT pkey_device_authorizations PRIMARY KEY (id),
+    CONSTRAINT fkey_device_authorizations_to_applications FOREIGN KEY (application_id)
+    REFERENCES applications (id) ON DELETE CASCADE,
+    CONSTRAINT fkey_device_authorizations_to_authorizations FOREIGN KEY (authorization_id)
+    REFERENCES authorizations (id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX index_device_authorizations_on_device_code ON device_authorizations USING btree (device_code);
+CREATE UNIQUE INDEX index_device_authorizations_on_user_code ON device_authorizations USING btree (user_code);
+
+SELECT manage_updated_at('device_authorizations');
+SELECT manage_versions('device_authorizations');
@@ -0,0 +1,5 @@
+ALTER TABLE applications ADD COLUMN redirect_url varchar NULL;
+
+UPDATE applications SET redirect_url = COALESCE(redirect_urls[1], '');
+
+ALTER TABLE applications ALTER COLUMN redirect_url SET NOT NULL, DROP COLUMN redirect_urls;
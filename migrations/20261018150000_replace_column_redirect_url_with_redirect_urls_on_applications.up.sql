@@ -0,0 +1,5 @@
+ALTER TABLE applications ADD COLUMN redirect_urls varchar[] NOT NULL DEFAULT '{}';
+
+UPDATE applications SET redirect_urls = ARRAY[redirect_url];
+
+ALTER TABLE applications DROP COLUMN redirect_url;
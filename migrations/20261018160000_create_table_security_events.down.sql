@@ -0,0 +1,3 @@
+DROP TABLE security_events;
+
+DROP TYPE security_event_kind;
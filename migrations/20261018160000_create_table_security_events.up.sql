@@ -0,0 +1,22 @@
+CREATE TYPE security_event_kind AS ENUM ('refresh_token_reused');
+
+CREATE TABLE security_events (
+    id uuid NOT NULL DEFAULT gen_random_uuid(),
+    user_id uuid NOT NULL,
+    application_id uuid NULL,
+    authorization_id uuid NULL,
+    kind security_event_kind NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT current_timestamp,
+    updated_at timestamptz NULL,
+    CONSTRAINT pkey_security_events PRIMARY KEY (id),
+    CONSTRAINT fkey_security_events_to_users FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE,
+    CONSTRAINT fkey_security_events_to_applications FOREIGN KEY (application_id) REFERENCES applications (id)
+    ON DELETE SET NULL,
+    CONSTRAINT fkey_security_events_to_authorizations FOREIGN KEY (authorization_id) REFERENCES authorizations (id)
+    ON DELETE SET NULL
+);
+
+CREATE INDEX index_security_events_on_user_id ON security_events USING btree (user_id);
+
+SELECT manage_updated_at('security_events');
+SELECT manage_versions('security_events');
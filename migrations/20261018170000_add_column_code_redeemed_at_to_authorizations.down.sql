@@ -0,0 +1,11 @@
+DELETE FROM security_events WHERE kind = 'authorization_code_reused';
+
+ALTER TYPE security_event_kind RENAME TO security_event_kind_old;
+
+CREATE TYPE security_event_kind AS ENUM ('refresh_token_reused');
+
+ALTER TABLE security_events ALTER COLUMN kind TYPE security_event_kind USING kind::text::security_event_kind;
+
+DROP TYPE security_event_kind_old;
+
+ALTER TABLE authorizations DROP COLUMN code_redeemed_at;
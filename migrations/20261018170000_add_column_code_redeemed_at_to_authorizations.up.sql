@@ -0,0 +1,3 @@
+ALTER TABLE authorizations ADD COLUMN code_redeemed_at timestamptz NULL;
+
+ALTER TYPE security_event_kind ADD VALUE 'authorization_code_reused';
@@ -0,0 +1 @@
+ALTER TABLE authorizations DROP COLUMN state;
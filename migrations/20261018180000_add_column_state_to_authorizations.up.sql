@@ -0,0 +1 @@
+ALTER TABLE authorizations ADD COLUMN state varchar NULL;
@@ -0,0 +1 @@
+ALTER TABLE applications DROP COLUMN jwt_access_tokens_at;
@@ -0,0 +1 @@
+ALTER TABLE applications ADD COLUMN jwt_access_tokens_at timestamptz NULL;
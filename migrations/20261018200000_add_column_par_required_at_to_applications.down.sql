@@ -0,0 +1 @@
+ALTER TABLE applications DROP COLUMN par_required_at;
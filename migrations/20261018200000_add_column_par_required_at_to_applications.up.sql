@@ -0,0 +1 @@
+ALTER TABLE applications ADD COLUMN par_required_at timestamptz NULL;
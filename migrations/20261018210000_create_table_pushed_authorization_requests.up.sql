@@ -0,0 +1,25 @@
+CREATE TABLE pushed_authorization_requests (
+    id uuid NOT NULL DEFAULT gen_random_uuid(),
+    application_id uuid NOT NULL,
+    request_uri citext NOT NULL,
+    redirect_url varchar NOT NULL,
+    code_challenge varchar NOT NULL,
+    scope varchar NULL,
+    nonce varchar NULL,
+    state varchar NULL,
+    prompt varchar NULL,
+    max_age integer NULL,
+    login_hint varchar NULL,
+    expires_at timestamptz NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT current_timestamp,
+    updated_at timestamptz NULL,
+    CONSTRAINT pkey_pushed_authorization_requests PRIMARY KEY (id),
+    CONSTRAINT fkey_pushed_authorization_requests_to_applications FOREIGN KEY (application_id)
+    REFERENCES applications (id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX index_pushed_authorization_requests_on_request_uri ON pushed_authorization_requests
+USING btree (request_uri);
+
+SELECT manage_updated_at('pushed_authorization_requests');
+SELECT manage_versions('pushed_authorization_requests');
@@ -0,0 +1 @@
+ALTER TABLE application_tokens DROP COLUMN permissions;
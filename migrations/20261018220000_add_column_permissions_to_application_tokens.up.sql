@@ -0,0 +1 @@
+ALTER TABLE application_tokens ADD COLUMN permissions varchar[] NOT NULL DEFAULT '{}';
@@ -0,0 +1 @@
+ALTER TABLE applications DROP COLUMN backchannel_logout_url;
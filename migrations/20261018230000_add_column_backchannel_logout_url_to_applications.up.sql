@@ -0,0 +1 @@
+ALTER TABLE applications ADD COLUMN backchannel_logout_url varchar NULL;
@@ -0,0 +1,6 @@
+ALTER TABLE applications
+DROP COLUMN description,
+DROP COLUMN homepage_url,
+DROP COLUMN privacy_policy_url,
+DROP COLUMN terms_url,
+DROP COLUMN logo_updated_at;
@@ -0,0 +1,6 @@
+ALTER TABLE applications
+ADD COLUMN description text NULL,
+ADD COLUMN homepage_url varchar NULL,
+ADD COLUMN privacy_policy_url varchar NULL,
+ADD COLUMN terms_url varchar NULL,
+ADD COLUMN logo_updated_at timestamptz NULL;
@@ -0,0 +1,3 @@
+ALTER TABLE applications DROP COLUMN owner_id;
+
+ALTER TABLE users DROP COLUMN developer_at;
@@ -0,0 +1,6 @@
+ALTER TABLE users ADD COLUMN developer_at timestamptz NULL;
+
+ALTER TABLE applications ADD COLUMN owner_id uuid NULL,
+ADD CONSTRAINT fkey_applications_to_users FOREIGN KEY (owner_id) REFERENCES users (id) ON DELETE SET NULL;
+
+CREATE INDEX index_applications_on_owner_id ON applications USING btree (owner_id);
@@ -0,0 +1,5 @@
+ALTER TABLE access_tokens DROP COLUMN refresh_started_at;
+
+ALTER TABLE applications DROP COLUMN access_token_ttl_secs,
+DROP COLUMN refresh_token_ttl_secs,
+DROP COLUMN refresh_token_idle_ttl_secs;
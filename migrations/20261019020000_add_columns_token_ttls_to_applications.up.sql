@@ -0,0 +1,7 @@
+ALTER TABLE applications ADD COLUMN access_token_ttl_secs integer NULL,
+ADD COLUMN refresh_token_ttl_secs integer NULL,
+ADD COLUMN refresh_token_idle_ttl_secs integer NULL;
+
+ALTER TABLE access_tokens ADD COLUMN refresh_started_at timestamptz NOT NULL DEFAULT current_timestamp;
+
+UPDATE access_tokens SET refresh_started_at = created_at;
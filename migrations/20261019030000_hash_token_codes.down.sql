@@ -0,0 +1,6 @@
+-- Digests cannot be reversed, so revoke every token that can no longer be looked up.
+UPDATE access_tokens SET revoked_at = current_timestamp WHERE revoked_at IS NULL;
+
+UPDATE application_tokens SET revoked_at = current_timestamp WHERE revoked_at IS NULL;
+
+UPDATE authorizations SET revoked_at = current_timestamp WHERE revoked_at IS NULL;
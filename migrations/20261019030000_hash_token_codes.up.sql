@@ -0,0 +1,6 @@
+UPDATE access_tokens SET code = encode(sha256(convert_to(code::text, 'UTF8')), 'hex'),
+refresh_code = encode(sha256(convert_to(refresh_code::text, 'UTF8')), 'hex');
+
+UPDATE application_tokens SET code = encode(sha256(convert_to(code::text, 'UTF8')), 'hex');
+
+UPDATE authorizations SET code = encode(sha256(convert_to(code::text, 'UTF8')), 'hex');
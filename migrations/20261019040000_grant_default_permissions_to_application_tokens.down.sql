@@ -0,0 +1,3 @@
+UPDATE application_tokens SET permissions = array_remove(
+    array_remove(array_remove(permissions, 'users:read'), 'users:read_email'), 'tokens:introspect'
+);
@@ -0,0 +1,3 @@
+-- Existing tokens could read users and introspect tokens without permissions, so keep them working.
+UPDATE application_tokens SET permissions = '{users:read,users:read_email,tokens:introspect}'
+WHERE permissions = '{}';
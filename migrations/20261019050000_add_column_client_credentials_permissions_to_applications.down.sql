@@ -0,0 +1 @@
+ALTER TABLE applications DROP COLUMN client_credentials_permissions;
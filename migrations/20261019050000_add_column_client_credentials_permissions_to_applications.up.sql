@@ -0,0 +1,8 @@
+ALTER TABLE applications ADD COLUMN client_credentials_permissions varchar[] NOT NULL DEFAULT '{}';
+
+-- Applications already using the client_credentials grant keep the access their tokens had.
+UPDATE applications SET client_credentials_permissions = '{users:read,users:read_email,tokens:introspect}'
+WHERE owner_id IS NULL AND EXISTS (
+    SELECT 1 FROM application_tokens
+    WHERE application_tokens.application_id = applications.id AND application_tokens.name LIKE 'client\_credentials\_%'
+);
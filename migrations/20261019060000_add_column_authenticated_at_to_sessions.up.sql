@@ -0,0 +1,3 @@
+ALTER TABLE sessions ADD COLUMN authenticated_at timestamptz NOT NULL DEFAULT current_timestamp;
+
+UPDATE sessions SET authenticated_at = created_at;
@@ -0,0 +1 @@
+ALTER TABLE device_authorizations DROP COLUMN interval_secs;
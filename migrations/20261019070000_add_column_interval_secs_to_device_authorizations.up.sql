@@ -0,0 +1 @@
+ALTER TABLE device_authorizations ADD COLUMN interval_secs integer NOT NULL DEFAULT 5;
@@ -0,0 +1,12 @@
+DELETE FROM authorizations AS a
+WHERE a.redirect_url IS NULL AND EXISTS (
+    SELECT 1 FROM authorizations AS b
+    WHERE
+        b.application_id = a.application_id AND b.user_id = a.user_id AND b.id <> a.id
+        AND (b.redirect_url IS NOT NULL OR b.created_at > a.created_at)
+);
+
+DROP INDEX index_authorizations_on_application_id_user_id;
+
+CREATE UNIQUE INDEX index_authorizations_on_application_id_user_id ON authorizations
+USING btree (application_id, user_id);
@@ -0,0 +1,5 @@
+DROP INDEX index_authorizations_on_application_id_user_id;
+
+-- Device authorizations have no redirect URL and get their own row per approval.
+CREATE UNIQUE INDEX index_authorizations_on_application_id_user_id ON authorizations
+USING btree (application_id, user_id) WHERE redirect_url IS NOT NULL;
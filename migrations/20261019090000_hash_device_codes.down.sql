@@ -0,0 +1,2 @@
+-- Digests cannot be reversed, so drop every device authorization that can no longer be looked up.
+DELETE FROM device_authorizations;
@@ -0,0 +1 @@
+UPDATE device_authorizations SET device_code = encode(sha256(convert_to(device_code::text, 'UTF8')), 'hex');
@@ -0,0 +1,3 @@
+DROP INDEX index_access_tokens_on_authorization_code;
+
+ALTER TABLE access_tokens DROP COLUMN authorization_code;
@@ -0,0 +1,3 @@
+ALTER TABLE access_tokens ADD COLUMN authorization_code varchar NULL;
+
+CREATE INDEX index_access_tokens_on_authorization_code ON access_tokens USING btree (authorization_code);
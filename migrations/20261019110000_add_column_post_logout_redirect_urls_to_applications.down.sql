@@ -0,0 +1 @@
+ALTER TABLE applications DROP COLUMN post_logout_redirect_urls;
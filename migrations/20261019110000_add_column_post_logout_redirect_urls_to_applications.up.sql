@@ -0,0 +1 @@
+ALTER TABLE applications ADD COLUMN post_logout_redirect_urls varchar[] NOT NULL DEFAULT '{}';
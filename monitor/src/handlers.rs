@@ -1,10 +1,12 @@
 use std::net::IpAddr;
 use std::str::FromStr;
+use std::time::Duration;
 
 use apalis::prelude::BoxDynError;
+use reqwest::header::CONTENT_TYPE;
 
 use identity_core::commands;
-use identity_core::jobs::{NewConfirmationJob, NewSessionJob, NewUserJob, PasswordChangedJob};
+use identity_core::jobs::{NewConfirmationJob, NewSessionJob, NewUserJob, PasswordChangedJob, SessionFinishedJob};
 
 use crate::ip_geo::IpGeo;
 use crate::mailer::*;
@@ -66,3 +68,37 @@ pub async fn password_changed(job: PasswordChangedJob) -> Result<(), BoxDynError
 
     Ok(())
 }
+
+pub async fn session_finished(job: SessionFinishedJob) -> Result<(), BoxDynError> {
+    let session = commands::get_finished_session_by_id(job.session_id).await?;
+    let applications = commands::all_applications_with_backchannel_logout_by_session(&session).await?;
+    let client = reqwest::Client::builder().timeout(Duration::from_secs(10)).build()?;
+    let mut failed_count = 0;
+
+    for application in applications {
+        let Some(backchannel_logout_url) = application.backchannel_logout_url() else {
+            continue;
+        };
+
+        let logout_token = commands::encode_logout_token(&application, &session)?;
+        let result = client
+            .post(backchannel_logout_url)
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(format!("logout_token={logout_token}"))
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+
+        if let Err(err) = result {
+            tracing::warn!("Back-channel logout failed for application {application}: {err}");
+
+            failed_count += 1;
+        }
+    }
+
+    if failed_count > 0 {
+        return Err(format!("Back-channel logout failed for {failed_count} application(s)").into());
+    }
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use std::time::Duration;
 
 use apalis::layers::WorkerBuilderExt;
+use apalis::layers::retry::RetryPolicy;
 use apalis::layers::sentry::SentryLayer;
 use apalis::prelude::{Monitor, WorkerBuilder};
 use sentry::integrations::tower::NewSentryLayer;
@@ -16,6 +17,8 @@ mod handlers;
 mod ip_geo;
 mod mailer;
 
+const SESSION_FINISHED_RETRIES: usize = 5;
+
 #[tokio::main]
 async fn main() {
     let _guard = start_tracing_subscriber();
@@ -67,11 +70,23 @@ async fn main() {
             .build(handlers::password_changed)
     };
 
+    let session_finished_worker = |index| {
+        WorkerBuilder::new(format!("session-finished-{index}"))
+            .backend(jobs_storage.session_finished.clone())
+            .layer(NewSentryLayer::new_from_top())
+            .layer(SentryLayer::new())
+            .enable_tracing()
+            .retry(RetryPolicy::retries(SESSION_FINISHED_RETRIES))
+            .concurrency(1)
+            .build(handlers::session_finished)
+    };
+
     Monitor::new()
         .register(new_confirmation_worker)
         .register(new_session_worker)
         .register(new_user_worker)
         .register(password_changed_worker)
+        .register(session_finished_worker)
         .shutdown_timeout(Duration::from_millis(10000))
         .run_with_signal(async {
             info!("Monitor started");